@@ -5,11 +5,119 @@
  */
 
 mod io_util {
+    use rustyline::{
+        ColorMode, Config, Context, Editor, Helper,
+        completion::Completer,
+        error::ReadlineError,
+        highlight::{CmdKind, Highlighter},
+        hint::Hinter,
+        history::MemHistory,
+        validate::Validator,
+    };
     use std::{
+        borrow::Cow,
+        cell::RefCell,
+        collections::HashMap,
         fmt,
-        io::{self, Write},
+        io::{self, IsTerminal, Write},
+        ops::RangeBounds,
+        path::Path,
+        process,
+        str::FromStr,
+        sync::atomic::{AtomicBool, Ordering},
     };
 
+    /// Whether notices are printed to the standard error instead of the standard output.
+    static ARE_NOTICES_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+    /// The keyword that cancels any prompt that validates its response.
+    pub const CANCEL_KEYWORD: &str = "cancel";
+    /// The number of invalid responses after which a prompt is cancelled.
+    pub const MAX_ATTEMPTS: usize = 5;
+
+    /// Completes the input of a prompt with the inputs that the prompt accepts.
+    #[derive(Default)]
+    struct InputCompleter {
+        /// The inputs that the current prompt accepts.
+        candidates: Vec<String>,
+        /// Whether the current prompt's input is a secret, which is shown as asterisks.
+        is_masked: bool,
+    }
+    impl Completer for InputCompleter {
+        type Candidate = String;
+
+        fn complete(&self, line: &str, pos: usize, _: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+            let input = line[..pos].to_lowercase();
+
+            Ok((
+                0,
+                self.candidates
+                    .iter()
+                    .filter(|c| c.to_lowercase().starts_with(&input))
+                    .cloned()
+                    .collect(),
+            ))
+        }
+    }
+    impl Hinter for InputCompleter {
+        type Hint = String;
+    }
+    impl Highlighter for InputCompleter {
+        fn highlight<'l>(&self, line: &'l str, _: usize) -> Cow<'l, str> {
+            if self.is_masked {
+                Cow::Owned("*".repeat(line.chars().count()))
+            } else {
+                Cow::Borrowed(line)
+            }
+        }
+
+        fn highlight_char(&self, _: &str, _: usize, _: CmdKind) -> bool {
+            self.is_masked
+        }
+    }
+    impl Validator for InputCompleter {}
+    impl Helper for InputCompleter {}
+
+    /// An editor for the lines inputted into prompts, which remembers the inputs of each prompt separately.
+    struct LineEditor {
+        editor: Editor<InputCompleter, MemHistory>,
+        /// The previous inputs of each prompt, from oldest to newest.
+        histories: HashMap<String, Vec<String>>,
+    }
+
+    thread_local! {
+        /// The line editor, which is only created if the console is attached to a terminal.
+        static LINE_EDITOR: RefCell<Option<LineEditor>> = RefCell::new(
+            if io::stdin().is_terminal() && io::stdout().is_terminal() {
+                // Colors are forced so that secrets are always masked by the highlighter.
+                Editor::with_history(Config::builder().color_mode(ColorMode::Forced).build(), MemHistory::new())
+                    .ok()
+                    .map(|mut editor| {
+                        editor.set_helper(Some(InputCompleter::default()));
+
+                        LineEditor { editor, histories: HashMap::new() }
+                    })
+            } else {
+                None
+            }
+        );
+    }
+
+    /// Prints every notice from now on to the standard error, keeping the standard output free for responses.
+    pub fn send_notices_to_stderr() {
+        ARE_NOTICES_TO_STDERR.store(true, Ordering::Relaxed);
+    }
+
+    /// Prints a notice of work that the bank did on its own, such as its daily processing, or of a failure to persist
+    /// it.
+    pub fn notice(msg: &str) {
+        if ARE_NOTICES_TO_STDERR.load(Ordering::Relaxed) {
+            eprintln!("{msg}");
+        } else {
+            println!("{msg}");
+        }
+    }
+
     /// Prints an ordered list to the console.
     ///
     /// The list's elements are stringified and printed along with their index incremented by one (`i + 1`).
@@ -19,390 +127,7499 @@ pub fn print_ordered_list<T: fmt::Display>(list: &[T]) {
         }
     }
 
-    /// Prompts a CLI user to input a response.
+    /// Prompts a CLI user to input a response, which can be tab completed to one of the given inputs.
     ///
     /// A message is printed before awaiting the user's response, which is inputted on the same line in the console.
-    pub fn prompt(msg: &str) -> String {
+    /// When the console is attached to a terminal, the response can be edited with the arrow keys and the previous
+    /// responses to the same prompt can be recalled, unless it is masked. Otherwise, the response is read as a plain
+    /// line. The program exits once the input ends, as no response can be inputted anymore.
+    fn prompt_completing<S: AsRef<str>>(msg: &str, candidates: &[S], is_masked: bool) -> String {
+        let edited_input = LINE_EDITOR.with_borrow_mut(|line_editor| {
+            let LineEditor { editor, histories } = line_editor.as_mut()?;
+            let history = histories.entry(msg.to_string()).or_default();
+
+            let _ = editor.clear_history();
+
+            if !is_masked {
+                for entry in history.iter() {
+                    let _ = editor.add_history_entry(entry.as_str());
+                }
+            }
+
+            if let Some(completer) = editor.helper_mut() {
+                completer.candidates = candidates.iter().map(|c| c.as_ref().to_string()).collect();
+                completer.is_masked = is_masked;
+            }
+
+            match editor.readline(msg) {
+                Ok(line) => {
+                    let input = line.trim().to_string();
+
+                    if !is_masked && !input.is_empty() && history.last() != Some(&input) {
+                        history.push(input.clone());
+                    }
+
+                    Some(input)
+                }
+                Err(ReadlineError::Interrupted) => process::exit(130),
+                Err(ReadlineError::Eof) => process::exit(0),
+                Err(_) => Some(String::new()),
+            }
+        });
+
+        if let Some(input) = edited_input {
+            return input;
+        }
+
         print!("{msg}");
 
         io::stdout().flush().expect("Failed to flush the output string...");
 
         let mut input = String::new();
 
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read the input string...");
+        if io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read the input string...")
+            == 0
+        {
+            process::exit(0);
+        }
+
+        input.trim().to_string()
+    }
+
+    /// Prompts a CLI user to answer a yes or no question, repeating the prompt until either is answered.
+    ///
+    /// Returns `None` if the prompt is cancelled, or answered incorrectly too many times, so that callers can abort
+    /// rather than mistake it for either answer.
+    pub fn confirm(msg: &str) -> Option<bool> {
+        prompt_valid(msg, |input| match input.to_uppercase().as_str() {
+            "Y" => Ok(true),
+            "N" => Ok(false),
+            _ => Err(String::from("Only accepting a [Y]es or [N]o answer!")),
+        })
+    }
+
+    /// Prompts a CLI user to input a response until it is valid, returning `None` if the prompt is cancelled.
+    ///
+    /// The response is validated by `parse`, which returns the error to print if it is invalid. Inputting the
+    /// [`CANCEL_KEYWORD`], or an invalid response [`MAX_ATTEMPTS`] times, cancels the prompt.
+    pub fn prompt_valid<T>(msg: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+        prompt_valid_completing::<T, &str>(msg, &[], parse)
+    }
+
+    /// Prompts a CLI user to input a response until it is valid, which can be tab completed to one of the given inputs.
+    ///
+    /// See [`prompt_valid`] for how the response is validated and how the prompt is cancelled.
+    pub fn prompt_valid_completing<T, S: AsRef<str>>(
+        msg: &str,
+        candidates: &[S],
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) -> Option<T> {
+        prompt_valid_with(msg, candidates, false, parse)
+    }
+
+    /// Prompts a CLI user to input a secret until it is valid, which is masked as it is inputted into a terminal.
+    ///
+    /// See [`prompt_valid`] for how the response is validated and how the prompt is cancelled.
+    pub fn prompt_secret<T>(msg: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+        prompt_valid_with::<T, &str>(msg, &[], true, parse)
+    }
+
+    /// Prompts a CLI user to input a response until it is valid, as done by the other validating prompts.
+    fn prompt_valid_with<T, S: AsRef<str>>(
+        msg: &str,
+        candidates: &[S],
+        is_masked: bool,
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) -> Option<T> {
+        for _ in 0..MAX_ATTEMPTS {
+            let input = prompt_completing(msg, candidates, is_masked);
+
+            if input.eq_ignore_ascii_case(CANCEL_KEYWORD) {
+                println!("Cancelled!");
+
+                return None;
+            }
+
+            match parse(&input) {
+                Ok(value) => return Some(value),
+                Err(err) => {
+                    println!("{err}");
+
+                    println!();
+                }
+            }
+        }
+
+        println!("Too many invalid responses, cancelled!");
+
+        None
+    }
+
+    /// Prompts a CLI user to input a number within a range until it is valid, returning `None` if the prompt is
+    /// cancelled.
+    ///
+    /// `error` is printed whenever the response is not a number or is outside of the range. Floating point ranges should
+    /// be bounded to exclude the infinities, which are otherwise parsed as numbers.
+    pub fn prompt_number<T: FromStr + PartialOrd>(msg: &str, range: impl RangeBounds<T>, error: &str) -> Option<T> {
+        prompt_valid(msg, |input| match input.parse::<T>() {
+            Ok(number) if range.contains(&number) => Ok(number),
+            _ => Err(error.to_string()),
+        })
+    }
+
+    /// Prompts a CLI user to input the path of a file with one of the given extensions until it is valid, returning
+    /// `None` if the prompt is cancelled.
+    pub fn prompt_file(msg: &str, extensions: &[&str]) -> Option<String> {
+        prompt_valid(msg, |input| {
+            match Path::new(input).extension().and_then(|e| e.to_str()) {
+                Some(extension) if extensions.contains(&extension) => Ok(input.to_string()),
+                _ => {
+                    let names = extensions.iter().map(|e| format!(".{e}")).collect::<Vec<_>>();
+
+                    Err(match names.split_last() {
+                        Some((last, [])) => format!("File must be a {last} file!"),
+                        Some((last, rest)) => format!("File must be a {} or {last} file!", rest.join(", ")),
+                        None => String::from("No files can be inputted!"),
+                    })
+                }
+            }
+        })
+    }
+
+    /// Prompts a CLI user to choose an option by its ID, as printed by [`print_ordered_list`], until it is valid.
+    ///
+    /// The index of the chosen option is returned, or `None` if the prompt is cancelled.
+    pub fn prompt_option(msg: &str, option_cnt: usize, error: &str) -> Option<usize> {
+        prompt_valid(msg, |input| match input.parse::<usize>() {
+            Ok(id) if (1..=option_cnt).contains(&id) => Ok(id - 1),
+            Ok(_) => Err(error.to_string()),
+            Err(_) => Err(String::from("ID must be a positive whole number (integer)!")),
+        })
+    }
+}
+
+mod clock {
+    use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta};
+    use std::sync::{
+        OnceLock,
+        atomic::{AtomicI64, Ordering},
+    };
+
+    /// The date that the bank's clock was started at, if it does not follow the system's clock.
+    static START_DATE: OnceLock<NaiveDate> = OnceLock::new();
+    /// The number of days that the bank's clock was advanced by.
+    static ADVANCED_DAYS: AtomicI64 = AtomicI64::new(0);
+
+    /// Starts the bank's clock at a fixed date so that simulations run the same way every time.
+    ///
+    /// The clock then stays at the start of the bank's business hours until it is advanced.
+    pub fn start_at(date: NaiveDate) {
+        START_DATE.get_or_init(|| date);
+    }
+
+    /// The most days that the bank's clock can be advanced by at once.
+    pub const MAX_ADVANCE_DAYS: u32 = 36500;
+
+    /// Advances the bank's clock by a number of days.
+    pub fn advance_days(day_cnt: i64) {
+        ADVANCED_DAYS.fetch_add(day_cnt, Ordering::Relaxed);
+    }
+
+    /// Checks whether the bank's clock differs from the system's clock.
+    pub fn is_simulated() -> bool {
+        START_DATE.get().is_some() || ADVANCED_DAYS.load(Ordering::Relaxed) != 0
+    }
+
+    /// Gets the current date and time according to the bank's clock.
+    pub fn now() -> DateTime<Local> {
+        let base = match START_DATE.get() {
+            Some(date) => date
+                .and_time(NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default())
+                .and_local_timezone(Local)
+                .earliest()
+                .unwrap_or_else(Local::now),
+            None => Local::now(),
+        };
+
+        base + TimeDelta::days(ADVANCED_DAYS.load(Ordering::Relaxed))
+    }
+
+    /// Gets the current date according to the bank's clock.
+    pub fn today() -> NaiveDate {
+        now().date_naive()
+    }
+}
+
+mod config {
+    use crate::{AccountType, currency::CURRENCIES_CODES, withdrawal_limit::WithdrawalLimits};
+    use serde::Deserialize;
+    use std::{collections::HashMap, fs, path::PathBuf, sync::OnceLock};
+
+    /// The configuration that the bank was started with.
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    /// The interest rates of the bank's products.
+    #[derive(Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct InterestConfig {
+        /// The annual interest rate percentage of savings accounts.
+        pub savings_annual_rate: f64,
+        /// The annual interest rate percentage of time deposits.
+        pub time_deposit_annual_rate: f64,
+        /// The annual interest rate percentage charged daily on overdrawn balances.
+        pub overdraft_annual_rate: f64,
+    }
+    impl Default for InterestConfig {
+        fn default() -> Self {
+            InterestConfig {
+                savings_annual_rate: AccountType::SAVINGS_ANNUAL_INTEREST_RATE,
+                time_deposit_annual_rate: AccountType::TIME_DEPOSIT_ANNUAL_INTEREST_RATE,
+                overdraft_annual_rate: AccountType::OVERDRAFT_ANNUAL_INTEREST_RATE,
+            }
+        }
+    }
+
+    /// The currencies that the bank deals in.
+    #[derive(Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct CurrencyConfig {
+        /// The currency used when none is inputted.
+        pub default: String,
+        /// The codes of the currencies that can be transacted in.
+        pub enabled: Vec<String>,
+        /// The value in PHP of foreign currencies before any rates are recorded.
+        pub initial_rates: HashMap<String, f64>,
+    }
+    impl Default for CurrencyConfig {
+        fn default() -> Self {
+            CurrencyConfig {
+                default: String::from("PHP"),
+                enabled: CURRENCIES_CODES.iter().map(|c| c.to_string()).collect(),
+                initial_rates: HashMap::new(),
+            }
+        }
+    }
+
+    /// The withdrawal limits of each account type that can be withdrawn from.
+    #[derive(Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct LimitsConfig {
+        /// The withdrawal limits of savings accounts.
+        pub savings: WithdrawalLimits,
+        /// The withdrawal limits of checking accounts.
+        pub checking: WithdrawalLimits,
+        /// The withdrawal limits of time deposits.
+        pub time_deposit: WithdrawalLimits,
+    }
+    impl Default for LimitsConfig {
+        fn default() -> Self {
+            LimitsConfig {
+                savings: WithdrawalLimits::SAVINGS_DEFAULT,
+                checking: WithdrawalLimits::CHECKING_DEFAULT,
+                time_deposit: WithdrawalLimits::NONE,
+            }
+        }
+    }
+
+    /// The locations of the files that the bank reads and writes.
+    #[derive(Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct FilesConfig {
+        /// The file that the last known good exchange rates are cached to.
+        pub rate_cache: PathBuf,
+        /// The directory that receipts are saved to, if they are saved.
+        pub receipts_dir: Option<PathBuf>,
+        /// The file that the bank's accounts and rates are saved to, if they are saved.
+        pub data_file: Option<PathBuf>,
+        /// The file that every transaction is appended to, if they are journaled.
+        pub journal_file: Option<PathBuf>,
+        /// Whether the data and journal files are encrypted with a passphrase.
+        pub encrypt: bool,
+    }
+    impl Default for FilesConfig {
+        fn default() -> Self {
+            FilesConfig {
+                rate_cache: PathBuf::from("exchange_rates_cache.json"),
+                receipts_dir: None,
+                data_file: None,
+                journal_file: None,
+                encrypt: false,
+            }
+        }
+    }
+
+    /// The parameters of the bank, as read from a TOML file.
+    #[derive(Default, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct Config {
+        /// The interest rates of the bank's products.
+        pub interest: InterestConfig,
+        /// The currencies that the bank deals in.
+        pub currency: CurrencyConfig,
+        /// The withdrawal limits of each account type.
+        pub limits: LimitsConfig,
+        /// The locations of the bank's files.
+        pub files: FilesConfig,
+    }
+    impl Config {
+        /// Checks that every parameter is usable, returning a description of each one that is not.
+        fn validate(&self) -> Vec<String> {
+            let mut errors = Vec::<String>::new();
+
+            for (key, rate) in [
+                ("interest.savings_annual_rate", self.interest.savings_annual_rate),
+                (
+                    "interest.time_deposit_annual_rate",
+                    self.interest.time_deposit_annual_rate,
+                ),
+                ("interest.overdraft_annual_rate", self.interest.overdraft_annual_rate),
+            ] {
+                if !rate.is_finite() || !(0.0..=1.0).contains(&rate) {
+                    errors.push(format!("{key} must be a percentage from 0 to 1!"));
+                }
+            }
+
+            for code in &self.currency.enabled {
+                if !CURRENCIES_CODES.contains(&code.as_str()) {
+                    errors.push(format!("currency.enabled has the unknown currency \"{code}\"!"));
+                }
+            }
+
+            if !self.currency.enabled.iter().any(|c| c == "PHP") {
+                errors.push(String::from(
+                    "currency.enabled must have PHP, as every exchange rate is based on it!",
+                ));
+            }
+
+            if !self.currency.enabled.contains(&self.currency.default) {
+                errors.push(format!(
+                    "currency.default \"{}\" must be an enabled currency!",
+                    self.currency.default
+                ));
+            }
+
+            for (code, &rate) in &self.currency.initial_rates {
+                if !CURRENCIES_CODES.iter().skip(1).any(|c| c == code) {
+                    errors.push(format!(
+                        "currency.initial_rates has the unknown foreign currency \"{code}\"!"
+                    ));
+                } else if !rate.is_finite() || rate <= 0.0 {
+                    errors.push(format!("currency.initial_rates.{code} must be a positive number!"));
+                }
+            }
+
+            for (key, limits) in [
+                ("limits.savings", &self.limits.savings),
+                ("limits.checking", &self.limits.checking),
+                ("limits.time_deposit", &self.limits.time_deposit),
+            ] {
+                for (field, limit) in [
+                    ("max_single_amount", limits.max_single_amount),
+                    ("max_daily_amount", limits.max_daily_amount),
+                ] {
+                    if limit.is_some_and(|l| !l.is_finite() || l <= 0.0) {
+                        errors.push(format!("{key}.{field} must be a positive number!"));
+                    }
+                }
+
+                if limits.max_cnt == Some(0) {
+                    errors.push(format!("{key}.max_count must be a positive whole number!"));
+                }
+            }
+
+            if self.files.encrypt && self.files.data_file.is_none() && self.files.journal_file.is_none() {
+                errors.push(String::from(
+                    "files.encrypt needs a files.data_file or files.journal_file to encrypt!",
+                ));
+            }
+
+            errors
+        }
+    }
+
+    /// Loads the bank's configuration from a TOML file, returning every problem with it if it cannot be used.
+    pub fn load(path: &str) -> Result<(), Vec<String>> {
+        let contents = fs::read_to_string(path).map_err(|err| vec![format!("Failed to read {path}: {err}")])?;
+        let config =
+            toml::from_str::<Config>(&contents).map_err(|err| vec![format!("Failed to parse {path}: {err}")])?;
+        let errors = config.validate();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        CONFIG.get_or_init(|| config);
+
+        Ok(())
+    }
+
+    /// Gets the bank's configuration, which is the default one unless another was loaded.
+    pub fn get() -> &'static Config {
+        CONFIG.get_or_init(Config::default)
+    }
+}
+
+mod currency {
+    use crate::{
+        clock, config, fx_position,
+        io_util::{confirm, print_ordered_list, prompt_file, prompt_number, prompt_valid, prompt_valid_completing},
+    };
+    use chrono::{DateTime, Local, NaiveDate, TimeDelta};
+    use serde::{Deserialize, Serialize};
+    use std::{collections::HashMap, error, fs::File, path::Path};
+
+    /// The number of exchangeable currencies.
+    pub const CURRENCY_COUNT: usize = 6;
+    /// The titles or labels of the exchangeable currencies.
+    pub const CURRENCIES_TITLES: [&str; CURRENCY_COUNT] = [
+        "Philippine Peso (PHP)",
+        "United States Dollar (USD)",
+        "Japanese Yen (JPY)",
+        "British Pound Sterling (GBP)",
+        "Euro (EUR)",
+        "Chinese Yuan Renminni (CNY)",
+    ];
+    /// The [ISO 4217](https://en.wikipedia.org/wiki/ISO_4217) codes of the exchangeable currencies.
+    pub const CURRENCIES_CODES: [&str; CURRENCY_COUNT] = ["PHP", "USD", "JPY", "GBP", "EUR", "CNY"];
+
+    /// Checks whether a currency can be transacted in, as configured.
+    pub fn is_enabled(code: &str) -> bool {
+        config::get().currency.enabled.iter().any(|c| c == code)
+    }
+
+    /// Prompts a CLI user to input the code of an enabled currency, defaulting to the configured currency if nothing is
+    /// inputted.
+    pub fn prompt_currency(msg: &str) -> Option<&'static str> {
+        prompt_valid_completing(msg, &config::get().currency.enabled, |input| {
+            let input = input.to_uppercase();
+            let code = if input.is_empty() {
+                config::get().currency.default.as_str()
+            } else {
+                input.as_str()
+            };
+
+            match CURRENCIES_CODES.iter().find(|&&c| c == code) {
+                Some(&code) if is_enabled(code) => Ok(code),
+                Some(_) => Err(String::from("This currency is not enabled!")),
+                None => Err(String::from("No currency with this code exists!")),
+            }
+        })
+    }
+
+    /// Prompts a CLI user to choose an enabled currency by its ID in a list of currencies.
+    fn prompt_currency_option(msg: &str, codes: &[&'static str]) -> Option<&'static str> {
+        prompt_valid(msg, |input| match input.parse::<usize>() {
+            Ok(id) if (1..=codes.len()).contains(&id) => match codes[id - 1] {
+                code if is_enabled(code) => Ok(code),
+                _ => Err(String::from("This currency is not enabled!")),
+            },
+            Ok(_) => Err(String::from("No currency with this ID exists!")),
+            Err(_) => Err(String::from("ID must be a positive whole number (integer)!")),
+        })
+    }
+
+    /// Converts an amount from one currency to another.
+    ///
+    /// Every rate is the value in PHP of one unit of its currency, so the amount is converted to PHP and then divided by
+    /// the exchanged currency's rate.
+    pub fn convert(amount: f64, src: &&str, dest: &&str, rates: &HashMap<&str, f64>) -> f64 {
+        let src_php_amount = if *src == "PHP" { amount } else { amount * rates[src] };
+
+        if *dest == "PHP" {
+            src_php_amount
+        } else {
+            src_php_amount / rates[dest]
+        }
+    }
+
+    /// Prompts a CLI user to input an amount and what currencies to exchange, returning the source currency, amount and
+    /// exchanged currency.
+    fn prompt_exchange() -> Option<(&'static str, f64, &'static str)> {
+        println!("Source Currency Options:");
+        print_ordered_list(&CURRENCIES_TITLES);
+
+        println!();
+
+        let src = prompt_currency_option("Source Currency: ", &CURRENCIES_CODES)?;
+        let src_amount = prompt_number(
+            "Source Amount: ",
+            f64::MIN_POSITIVE..=f64::MAX,
+            "Amount must be a positive floating point number!",
+        )?;
+
+        println!();
+
+        println!("Exchanged Currency Options:");
+        print_ordered_list(&CURRENCIES_TITLES);
+
+        println!();
+
+        let dest = prompt_currency_option("Exchange Currency: ", &CURRENCIES_CODES)?;
+
+        Some((src, src_amount, dest))
+    }
+
+    /// Exchanges an amount of one currency for another at the current rates and prints the exchanged amount.
+    ///
+    /// The user is prompted to input the amount and what currencies to exchange.
+    pub fn exchange(rates: &HashMap<&str, f64>) {
+        let Some((src, src_amount, dest)) = prompt_exchange() else {
+            return;
+        };
+
+        let dest_amount = convert(src_amount, &src, &dest, rates);
+        let php_amount = convert(src_amount, &src, &"PHP", rates);
+
+        fx_position::book(src, src_amount, php_amount);
+        fx_position::book(dest, -dest_amount, php_amount);
+
+        println!("Exchange Amount: {dest_amount}");
+    }
+
+    /// How long an exchange quote can be executed for after it is issued.
+    pub const QUOTE_VALIDITY: TimeDelta = TimeDelta::minutes(5);
+
+    /// An exchange rate locked for a conversion until it expires.
+    pub struct ExchangeQuote {
+        /// The number that identifies the quote.
+        pub id: String,
+        /// The currency to convert from.
+        pub src: &'static str,
+        /// The currency to convert to.
+        pub dest: &'static str,
+        /// The amount to convert, in the source currency.
+        pub amount: f64,
+        /// How much one unit of the source currency is worth in the exchanged currency.
+        pub rate: f64,
+        /// When the quote stops being executable.
+        pub expires_at: DateTime<Local>,
+        /// Whether the quote has already been executed.
+        pub is_executed: bool,
+    }
+    impl ExchangeQuote {
+        /// Gets the amount that the conversion results in, in the exchanged currency.
+        pub fn exchange_amount(&self) -> f64 {
+            self.amount * self.rate
+        }
+
+        /// Prints the quote's terms.
+        fn print(&self) {
+            println!("Quote ID: {}", self.id);
+            println!("Rate: 1 {} = {} {}", self.src, self.rate, self.dest);
+            println!("Source Amount: {} {}", self.amount, self.src);
+            println!("Exchange Amount: {} {}", self.exchange_amount(), self.dest);
+            println!("Expires At: {}", self.expires_at.format("%Y-%m-%d %H:%M:%S"));
+        }
+    }
+
+    /// The exchange quotes issued during the session.
+    #[derive(Default)]
+    pub struct QuoteBook {
+        /// Every quote issued, from oldest to newest.
+        quotes: Vec<ExchangeQuote>,
+    }
+    impl QuoteBook {
+        /// Issues a quote that locks the current rate for a conversion.
+        ///
+        /// The rate is the source currency's value in PHP over the exchanged currency's, so a quote from PHP locks the
+        /// inverse of the exchanged currency's rate.
+        fn issue(
+            &mut self,
+            src: &'static str,
+            amount: f64,
+            dest: &'static str,
+            rates: &HashMap<&str, f64>,
+        ) -> &ExchangeQuote {
+            self.quotes.push(ExchangeQuote {
+                id: format!("Q{:04}", self.quotes.len() + 1),
+                src,
+                dest,
+                amount,
+                rate: convert(1.0, &src, &dest, rates),
+                expires_at: clock::now() + QUOTE_VALIDITY,
+                is_executed: false,
+            });
+
+            &self.quotes[self.quotes.len() - 1]
+        }
+
+        /// Requests a quote for a conversion.
+        ///
+        /// The user is prompted to input the amount and what currencies to exchange.
+        pub fn request_quote(&mut self, rates: &HashMap<&str, f64>) {
+            let Some((src, src_amount, dest)) = prompt_exchange() else {
+                return;
+            };
+
+            println!();
+
+            self.issue(src, src_amount, dest, rates).print();
+        }
+
+        /// Executes a quote's conversion at its locked rate.
+        ///
+        /// The user is prompted to input the quote's ID. If the quote has expired, a new quote is issued at the current
+        /// rates for the user to accept instead.
+        pub fn execute_quote(&mut self, rates: &HashMap<&str, f64>) {
+            let Some(idx) = prompt_valid("Quote ID: ", |input| {
+                self.quotes
+                    .iter()
+                    .position(|q| q.id == input.to_uppercase())
+                    .ok_or_else(|| String::from("No quote with this ID exists!"))
+            }) else {
+                return;
+            };
+
+            if self.quotes[idx].is_executed {
+                println!("This quote has already been executed!");
+
+                return;
+            }
+
+            println!();
+
+            let idx = if clock::now() <= self.quotes[idx].expires_at {
+                idx
+            } else {
+                let ExchangeQuote { src, amount, dest, .. } = self.quotes[idx];
+
+                println!("The quote has expired, so it was re-quoted at the current rates:");
+                self.issue(src, amount, dest, rates).print();
+
+                println!();
+
+                if confirm("Execute the new quote? (Y/N): ") != Some(true) {
+                    println!("The quote was not executed.");
+
+                    return;
+                }
+
+                println!();
+
+                self.quotes.len() - 1
+            };
+            let quote = &mut self.quotes[idx];
+
+            quote.is_executed = true;
+
+            // The bank receives the source currency and pays out the exchanged currency, both valued in PHP.
+            let php_amount = convert(quote.amount, &quote.src, &"PHP", rates);
+
+            fx_position::book(quote.src, quote.amount, php_amount);
+            fx_position::book(quote.dest, -quote.exchange_amount(), php_amount);
+
+            println!(
+                "Exchanged {} {} to {} {} at the quoted rate of {}",
+                quote.amount,
+                quote.src,
+                quote.exchange_amount(),
+                quote.dest,
+                quote.rate
+            );
+        }
+    }
+
+    /// Updates the exchange rate between a currency and Philippine Pesos.
+    ///
+    /// The user is prompted to input the currency and its value in PHP. The new rate is in effect from today.
+    pub fn set_exchange_rates(rates: &mut HashMap<&str, f64>, rate_dates: &mut HashMap<&str, NaiveDate>) {
+        print_ordered_list(&CURRENCIES_TITLES[1..]);
+
+        println!();
+
+        let Some(code) = prompt_currency_option("Select Foreign Currency: ", &CURRENCIES_CODES[1..]) else {
+            return;
+        };
+        let Some(rate) = prompt_number(
+            "Exchange Rate: ",
+            f64::MIN_POSITIVE..=f64::MAX,
+            "Exchange rate must be a positive floating point number!",
+        ) else {
+            return;
+        };
+
+        rates.insert(code, rate);
+        rate_dates.insert(code, clock::today());
+    }
+
+    /// The largest relative gain or loss of a round trip for the rates to still be considered consistent.
+    pub const ROUND_TRIP_TOLERANCE: f64 = 0.001;
+
+    /// A conversion from one currency through two others and back to itself.
+    pub struct RoundTrip {
+        /// The currencies converted through, in order, starting with the currency converted back to.
+        pub path: [&'static str; 3],
+        /// The relative gain of the round trip, which is negative for a loss.
+        pub gain: f64,
+    }
+
+    /// The exchange rates quoted in a rate sheet, keyed by the quoted currency and the currency it is quoted in.
+    pub type QuoteTable = HashMap<(&'static str, &'static str), f64>;
+
+    /// Gets the value of one unit of a currency in another from the quotes.
+    ///
+    /// If only the reverse pair is quoted, the inverse of its rate is used.
+    fn quoted_rate(quotes: &QuoteTable, code: &'static str, base: &'static str) -> Option<f64> {
+        quotes
+            .get(&(code, base))
+            .copied()
+            .or_else(|| quotes.get(&(base, code)).map(|rate| 1.0 / rate))
+    }
+
+    /// Computes the gain of converting through every triangle of distinct currencies (A → B → C → A).
+    ///
+    /// Only triangles whose three legs are all quoted are computed, since rates derived through PHP always round trip
+    /// exactly. Rotations of the same triangle have the same gain, so each triangle is only computed once per direction,
+    /// starting from its currency listed first in [`CURRENCIES_CODES`].
+    pub fn triangular_round_trips(quotes: &QuoteTable) -> Vec<RoundTrip> {
+        let mut round_trips = Vec::<RoundTrip>::new();
+
+        for (i, &a) in CURRENCIES_CODES.iter().enumerate() {
+            for &b in CURRENCIES_CODES.iter().skip(i + 1) {
+                for &c in CURRENCIES_CODES.iter().skip(i + 1) {
+                    if b == c {
+                        continue;
+                    }
+
+                    let (Some(ab), Some(bc), Some(ca)) = (
+                        quoted_rate(quotes, a, b),
+                        quoted_rate(quotes, b, c),
+                        quoted_rate(quotes, c, a),
+                    ) else {
+                        continue;
+                    };
+
+                    round_trips.push(RoundTrip {
+                        path: [a, b, c],
+                        gain: ab * bc * ca - 1.0,
+                    });
+                }
+            }
+        }
+
+        round_trips
+    }
+
+    /// Finds the triangular round trips whose gain or loss exceeds the tolerance.
+    pub fn inconsistent_round_trips(quotes: &QuoteTable, tolerance: f64) -> Vec<RoundTrip> {
+        triangular_round_trips(quotes)
+            .into_iter()
+            .filter(|t| t.gain.is_nan() || t.gain.abs() > tolerance)
+            .collect()
+    }
+
+    /// Prints a table of round trips and their gains.
+    fn print_round_trips(round_trips: &[RoundTrip]) {
+        println!("Round Trip               | Gain      |");
+
+        for RoundTrip { path: [a, b, c], gain } in round_trips {
+            println!(
+                "{trip:<24} | {gain:>+8.3}% |",
+                trip = format!("{a} -> {b} -> {c} -> {a}"),
+                gain = gain * 100.0
+            );
+        }
+    }
+
+    /// Checks every triangular round trip quoted in a rate sheet file for gains or losses exceeding the tolerance and
+    /// prints those found.
+    ///
+    /// The user is prompted to input the path of the file. Only the most recent valid quote of each currency pair is
+    /// checked.
+    pub fn check_rate_consistency() {
+        let Some(path) = prompt_file("Rate Sheet File (.csv/.json): ", &["csv", "json"]) else {
+            return;
+        };
+
+        let rows = match read_rate_sheet(&path) {
+            Ok(rows) => rows,
+            Err(err) => {
+                println!("Failed to read the rate sheet: {err}");
+
+                return;
+            }
+        };
+
+        let (latest_quotes, _) = validate_rate_sheet(rows, clock::today());
+        let quotes = quote_table(&latest_quotes);
+        let round_trip_cnt = triangular_round_trips(&quotes).len();
+        let inconsistent_trips = inconsistent_round_trips(&quotes, ROUND_TRIP_TOLERANCE);
+
+        println!();
+
+        println!("Tolerance: {}%", ROUND_TRIP_TOLERANCE * 100.0);
+
+        println!();
+
+        if round_trip_cnt == 0 {
+            println!("The rate sheet quotes no round trips, so its rates are consistent.");
+        } else if inconsistent_trips.is_empty() {
+            println!("All {round_trip_cnt} round trips are consistent.");
+        } else {
+            println!(
+                "{} of {round_trip_cnt} round trips are inconsistent:",
+                inconsistent_trips.len()
+            );
+            print_round_trips(&inconsistent_trips);
+        }
+    }
+
+    /// The rates of currencies along with the dates from which they are in effect.
+    pub type DatedRates = HashMap<&'static str, (f64, NaiveDate)>;
+
+    /// The quotes of currency pairs along with the dates from which they are in effect.
+    pub type DatedQuotes = HashMap<(&'static str, &'static str), (f64, NaiveDate)>;
+
+    /// Drops the effective dates of quotes.
+    pub fn quote_table(quotes: &DatedQuotes) -> QuoteTable {
+        quotes.iter().map(|(&pair, &(rate, _))| (pair, rate)).collect()
+    }
+
+    /// Gets the currency that rate sheet rows are quoted in when none is listed.
+    fn default_rate_base() -> String {
+        String::from("PHP")
+    }
+
+    /// A single row of an exchange rate sheet file.
+    #[derive(Debug, Clone, Deserialize, Serialize)]
+    pub struct RateSheetEntry {
+        /// The ISO 4217 code of the foreign currency.
+        pub code: String,
+        /// The ISO 4217 code of the currency that the rate is quoted in, which is Philippine Pesos unless listed.
+        #[serde(default = "default_rate_base")]
+        pub base: String,
+        /// The value of one unit of the foreign currency in the currency it is quoted in.
+        pub rate: f64,
+        /// The date from which the rate is in effect.
+        pub effective_date: NaiveDate,
+    }
+
+    /// Reads the rows of a CSV or JSON rate sheet file.
+    ///
+    /// The format is chosen from the file's extension. Malformed CSV rows are returned as errors instead of failing the
+    /// whole file.
+    pub fn read_rate_sheet(path: &str) -> Result<Vec<Result<RateSheetEntry, String>>, Box<dyn error::Error>> {
+        match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("csv") => Ok(csv::Reader::from_path(path)?
+                .deserialize::<RateSheetEntry>()
+                .map(|r| r.map_err(|err| err.to_string()))
+                .collect()),
+            Some("json") => Ok(serde_json::from_reader::<_, Vec<RateSheetEntry>>(File::open(path)?)?
+                .into_iter()
+                .map(Ok)
+                .collect()),
+            _ => Err("Rate sheet must be a .csv or .json file!".into()),
+        }
+    }
+
+    /// Writes rows to a CSV or JSON rate sheet file.
+    ///
+    /// The format is chosen from the file's extension.
+    fn write_rate_sheet(path: &str, entries: &[RateSheetEntry]) -> Result<(), Box<dyn error::Error>> {
+        match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("csv") => {
+                let mut fw = csv::Writer::from_path(path)?;
+
+                for entry in entries {
+                    fw.serialize(entry)?;
+                }
+
+                fw.flush()?;
+            }
+            Some("json") => serde_json::to_writer_pretty(File::create(path)?, entries)?,
+            _ => return Err("Rate sheet must be a .csv or .json file!".into()),
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a rate sheet row can be recorded, returning the codes of its currency and the currency it is quoted
+    /// in.
+    fn validate_rate_sheet_entry(
+        entry: &RateSheetEntry,
+        today: NaiveDate,
+    ) -> Result<(&'static str, &'static str), String> {
+        let code = entry.code.trim().to_uppercase();
+        let base = entry.base.trim().to_uppercase();
+
+        let Some(&code) = CURRENCIES_CODES.iter().skip(1).find(|&&c| c == code) else {
+            return Err(format!("No foreign currency with the code \"{code}\" exists!"));
+        };
+        let Some(&base) = CURRENCIES_CODES.iter().find(|&&c| c == base) else {
+            return Err(format!("No currency with the code \"{base}\" exists!"));
+        };
+
+        if base == code {
+            return Err(format!("Exchange rate of {code} must be quoted in another currency!"));
+        }
+
+        if !entry.rate.is_finite() || entry.rate <= 0.0 {
+            return Err(format!("Exchange rate of {code} must be a positive number!"));
+        }
+
+        if entry.effective_date > today {
+            return Err(format!(
+                "Exchange rate of {code} is not in effect until {}!",
+                entry.effective_date
+            ));
+        }
+
+        Ok((code, base))
+    }
+
+    /// Validates the rows of a rate sheet, returning the most recent quote of each currency pair and the rejected rows.
+    ///
+    /// Invalid rows and rows sharing a currency pair and effective date are rejected along with their row number.
+    pub fn validate_rate_sheet(
+        rows: Vec<Result<RateSheetEntry, String>>,
+        today: NaiveDate,
+    ) -> (DatedQuotes, Vec<(usize, String)>) {
+        let mut rejected_rows = Vec::<(usize, String)>::new();
+        let mut valid_rows = Vec::<(usize, (&str, &str), f64, NaiveDate)>::new();
+
+        for (i, row) in rows.into_iter().enumerate() {
+            match row.and_then(|e| validate_rate_sheet_entry(&e, today).map(|p| (p, e.rate, e.effective_date))) {
+                Ok((pair, rate, date)) => valid_rows.push((i + 1, pair, rate, date)),
+                Err(reason) => rejected_rows.push((i + 1, reason)),
+            }
+        }
+
+        let mut date_cnts = HashMap::<((&str, &str), NaiveDate), usize>::new();
+
+        for &(_, pair, _, date) in &valid_rows {
+            *date_cnts.entry((pair, date)).or_default() += 1;
+        }
+
+        valid_rows.retain(|&(row_num, (code, base), _, date)| {
+            if date_cnts[&((code, base), date)] > 1 {
+                rejected_rows.push((
+                    row_num,
+                    if base == "PHP" {
+                        format!("Exchange rate of {code} is listed more than once for {date}!")
+                    } else {
+                        format!("Exchange rate of {code} in {base} is listed more than once for {date}!")
+                    },
+                ));
+
+                false
+            } else {
+                true
+            }
+        });
+
+        rejected_rows.sort();
+
+        let mut latest_quotes = DatedQuotes::new();
+
+        for (_, pair, rate, date) in valid_rows {
+            if latest_quotes
+                .get(&pair)
+                .is_none_or(|&(_, latest_date)| date > latest_date)
+            {
+                latest_quotes.insert(pair, (rate, date));
+            }
+        }
+
+        (latest_quotes, rejected_rows)
+    }
+
+    /// Converts quotes to the value of each foreign currency in PHP.
+    ///
+    /// A currency quoted in PHP keeps that quote. Otherwise, its most recent quote in another currency is converted at
+    /// that currency's PHP quote, or at its current rate if the sheet does not quote it in PHP.
+    pub fn php_rates(quotes: &DatedQuotes, rates: &HashMap<&str, f64>) -> DatedRates {
+        let mut php_rates = DatedRates::new();
+
+        for &code in CURRENCIES_CODES.iter().skip(1) {
+            if let Some(&quote) = quotes.get(&(code, "PHP")) {
+                php_rates.insert(code, quote);
+            }
+        }
+
+        for &code in CURRENCIES_CODES.iter().skip(1) {
+            if php_rates.contains_key(code) {
+                continue;
+            }
+
+            let cross_quote = CURRENCIES_CODES
+                .iter()
+                .skip(1)
+                .filter_map(|&base| quotes.get(&(code, base)).map(|&(rate, date)| (base, rate, date)))
+                .reduce(|latest, quote| if quote.2 > latest.2 { quote } else { latest });
+
+            if let Some((base, rate, date)) = cross_quote {
+                let base_rate = quotes.get(&(base, "PHP")).map_or(rates[base], |&(rate, _)| rate);
+
+                php_rates.insert(code, (rate * base_rate, date));
+            }
+        }
+
+        php_rates
+    }
+
+    /// Loads exchange rates from a rate sheet file and prints a summary of what changed.
+    ///
+    /// The user is prompted to input the path of the file. Of the rows that pass [`validate_rate_sheet`], the most recent
+    /// one of each currency is recorded in PHP by [`php_rates`] unless the current rate is more recent. If the quotes
+    /// make any round trip inconsistent, the user is warned and asked to confirm first.
+    pub fn import_exchange_rates(rates: &mut HashMap<&str, f64>, rate_dates: &mut HashMap<&str, NaiveDate>) {
+        let Some(path) = prompt_file("Rate Sheet File (.csv/.json): ", &["csv", "json"]) else {
+            return;
+        };
+
+        let rows = match read_rate_sheet(&path) {
+            Ok(rows) => rows,
+            Err(err) => {
+                println!("Failed to read the rate sheet: {err}");
+
+                return;
+            }
+        };
+
+        let (latest_quotes, rejected_rows) = validate_rate_sheet(rows, clock::today());
+        let inconsistent_trips = inconsistent_round_trips(&quote_table(&latest_quotes), ROUND_TRIP_TOLERANCE);
+
+        if !inconsistent_trips.is_empty() {
+            println!();
+
+            println!("Warning: The rate sheet makes the following round trips inconsistent:");
+            print_round_trips(&inconsistent_trips);
+
+            println!();
+
+            if confirm("Record these exchange rates anyway? (Y/N): ") != Some(true) {
+                println!("The exchange rates were not recorded.");
+
+                return;
+            }
+        }
+
+        let latest_rates = php_rates(&latest_quotes, rates);
+
+        println!();
+
+        println!("Import Summary:");
+
+        for code in CURRENCIES_CODES.iter().skip(1) {
+            let Some(&(rate, date)) = latest_rates.get(code) else {
+                continue;
+            };
+            let curr_rate = rates[code];
+
+            if let Some(&curr_date) = rate_dates.get(code)
+                && date < curr_date
+            {
+                println!("{code}: Kept {curr_rate} (the sheet's rate from {date} is older than {curr_date})");
+            } else if rate == curr_rate {
+                println!("{code}: Unchanged at {rate} (effective {date})");
+
+                rate_dates.insert(code, date);
+            } else {
+                println!("{code}: {curr_rate} -> {rate} (effective {date})");
+
+                rates.insert(code, rate);
+                rate_dates.insert(code, date);
+            }
+        }
+
+        if latest_rates.is_empty() {
+            println!("No exchange rates were recorded!");
+        }
+
+        if !rejected_rows.is_empty() {
+            println!();
+
+            println!("Rejected Rows:");
+
+            for (row_num, reason) in rejected_rows {
+                println!("Row {row_num}: {reason}");
+            }
+        }
+    }
+
+    /// Saves the current exchange rates to a rate sheet file.
+    ///
+    /// The user is prompted to input the path of the file, which can be loaded back with [`import_exchange_rates`].
+    /// Rates that were never recorded are exported as being in effect today.
+    pub fn export_exchange_rates(rates: &HashMap<&str, f64>, rate_dates: &HashMap<&str, NaiveDate>) {
+        let Some(path) = prompt_file("Rate Sheet File (.csv/.json): ", &["csv", "json"]) else {
+            return;
+        };
+        let today = clock::today();
+
+        let entries = CURRENCIES_CODES
+            .iter()
+            .skip(1)
+            .map(|&code| RateSheetEntry {
+                code: code.to_string(),
+                base: default_rate_base(),
+                rate: rates[code],
+                effective_date: rate_dates.get(code).copied().unwrap_or(today),
+            })
+            .collect::<Vec<RateSheetEntry>>();
+
+        match write_rate_sheet(&path, &entries) {
+            Ok(()) => println!("Exported {} exchange rates to {path}", entries.len()),
+            Err(err) => println!("Failed to write the rate sheet: {err}"),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn entry(code: &str, base: &str, rate: f64) -> Result<RateSheetEntry, String> {
+            Ok(RateSheetEntry {
+                code: code.to_string(),
+                base: base.to_string(),
+                rate,
+                effective_date: NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            })
+        }
+
+        fn today() -> NaiveDate {
+            NaiveDate::from_ymd_opt(2020, 1, 2).unwrap()
+        }
+
+        #[test]
+        fn inconsistent_cross_quote_is_flagged() {
+            let quotes = QuoteTable::from([(("USD", "PHP"), 56.0), (("JPY", "PHP"), 0.38), (("JPY", "USD"), 0.0070)]);
+
+            let round_trips = triangular_round_trips(&quotes);
+            let inconsistent_trips = inconsistent_round_trips(&quotes, ROUND_TRIP_TOLERANCE);
+
+            assert_eq!(round_trips.len(), 2);
+            assert_eq!(inconsistent_trips.len(), 2);
+            assert_eq!(inconsistent_trips[0].path, ["PHP", "USD", "JPY"]);
+            assert!((inconsistent_trips[0].gain - (0.38 / (56.0 * 0.0070) - 1.0)).abs() < 1e-9);
+        }
+
+        #[test]
+        fn consistent_cross_quote_is_not_flagged() {
+            let quotes = QuoteTable::from([
+                (("USD", "PHP"), 56.0),
+                (("JPY", "PHP"), 0.38),
+                (("JPY", "USD"), 0.38 / 56.0),
+            ]);
+
+            assert_eq!(triangular_round_trips(&quotes).len(), 2);
+            assert!(inconsistent_round_trips(&quotes, ROUND_TRIP_TOLERANCE).is_empty());
+        }
+
+        #[test]
+        fn php_quotes_have_no_round_trips() {
+            let quotes = QuoteTable::from([(("USD", "PHP"), 56.0), (("JPY", "PHP"), 0.38)]);
+
+            assert!(triangular_round_trips(&quotes).is_empty());
+        }
+
+        #[test]
+        fn cross_quotes_are_converted_to_php() {
+            let (quotes, rejected_rows) = validate_rate_sheet(
+                vec![
+                    entry("USD", "PHP", 56.0),
+                    entry("JPY", "USD", 0.0070),
+                    entry("EUR", "GBP", 0.5),
+                    entry("GBP", "GBP", 1.0),
+                    entry("CNY", "XYZ", 1.0),
+                ],
+                today(),
+            );
+            let rates = HashMap::from([("USD", 50.0), ("JPY", 0.4), ("GBP", 70.0), ("EUR", 60.0), ("CNY", 8.0)]);
+            let php_rates = php_rates(&quotes, &rates);
+
+            assert_eq!(rejected_rows.len(), 2);
+            assert_eq!(php_rates["USD"].0, 56.0);
+            assert!((php_rates["JPY"].0 - 56.0 * 0.0070).abs() < 1e-9);
+            assert_eq!(php_rates["EUR"].0, 35.0);
+            assert!(!php_rates.contains_key("GBP"));
+        }
+    }
+}
+
+mod rate_provider {
+    use crate::{
+        config,
+        currency::{self, CURRENCIES_CODES, RateSheetEntry, RoundTrip},
+    };
+    use chrono::{DateTime, Local, NaiveDate, TimeDelta};
+    use serde::{Deserialize, Serialize};
+    use std::{
+        collections::HashMap,
+        error,
+        fs::{self, File},
+        io::{BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream, ToSocketAddrs},
+        path::PathBuf,
+        time::Duration,
+    };
+
+    /// A source that exchange rates can be fetched from.
+    pub trait RateProvider {
+        /// Describes where the rates are fetched from.
+        fn source(&self) -> &str;
+
+        /// Fetches the latest rate sheet rows from the source.
+        fn fetch(&self) -> Result<Vec<RateSheetEntry>, Box<dyn error::Error>>;
+    }
+
+    /// A provider that reads a CSV or JSON rate sheet file.
+    pub struct FileRateProvider {
+        /// The path of the rate sheet file.
+        path: String,
+    }
+    impl RateProvider for FileRateProvider {
+        fn source(&self) -> &str {
+            &self.path
+        }
+
+        fn fetch(&self) -> Result<Vec<RateSheetEntry>, Box<dyn error::Error>> {
+            Ok(currency::read_rate_sheet(&self.path)?
+                .into_iter()
+                .collect::<Result<Vec<RateSheetEntry>, String>>()?)
+        }
+    }
+
+    /// A provider that requests a JSON rate sheet from an HTTP endpoint.
+    ///
+    /// Only plain `http://` URLs are supported, and the response body must be a JSON array of rate sheet rows.
+    pub struct HttpRateProvider {
+        /// The URL of the endpoint.
+        url: String,
+    }
+    impl HttpRateProvider {
+        /// The maximum time to wait when connecting to or reading from the endpoint.
+        const TIMEOUT: Duration = Duration::from_secs(5);
+    }
+    impl RateProvider for HttpRateProvider {
+        fn source(&self) -> &str {
+            &self.url
+        }
+
+        fn fetch(&self) -> Result<Vec<RateSheetEntry>, Box<dyn error::Error>> {
+            let address = self
+                .url
+                .strip_prefix("http://")
+                .ok_or("Rate endpoint must be an http:// URL!")?;
+            let (host, path) = match address.split_once('/') {
+                Some((host, path)) => (host, format!("/{path}")),
+                None => (address, String::from("/")),
+            };
+            let socket_addr = if host.contains(':') {
+                host.to_socket_addrs()?
+            } else {
+                (host, 80).to_socket_addrs()?
+            }
+            .next()
+            .ok_or("Rate endpoint's host could not be resolved!")?;
+
+            let mut stream = TcpStream::connect_timeout(&socket_addr, HttpRateProvider::TIMEOUT)?;
+
+            stream.set_read_timeout(Some(HttpRateProvider::TIMEOUT))?;
+
+            // HTTP/1.0 keeps the server from sending a chunked body, which would not parse as JSON.
+            write!(
+                stream,
+                "GET {path} HTTP/1.0\r\nHost: {host}\r\nAccept: application/json\r\nConnection: close\r\n\r\n"
+            )?;
+
+            let mut response = String::new();
+
+            stream.read_to_string(&mut response)?;
+
+            let (head, body) = response.split_once("\r\n\r\n").ok_or("Malformed HTTP response!")?;
+            let status = head.split_whitespace().nth(1).unwrap_or_default();
+
+            if status != "200" {
+                return Err(format!("Rate endpoint responded with HTTP status {status}!").into());
+            }
+
+            Ok(serde_json::from_str(body)?)
+        }
+    }
+
+    /// Creates the provider for a rate source, which is either an `http://` URL or the path of a rate sheet file.
+    pub fn from_source(src: &str) -> Box<dyn RateProvider> {
+        if src.starts_with("http://") {
+            Box::new(HttpRateProvider { url: src.to_string() })
+        } else {
+            Box::new(FileRateProvider { path: src.to_string() })
+        }
+    }
+
+    /// The last rates successfully fetched from a provider.
+    #[derive(Deserialize, Serialize)]
+    struct RateSnapshot {
+        /// Where the rates were fetched from.
+        source: String,
+        /// When the rates were fetched.
+        fetched_at: DateTime<Local>,
+        /// The fetched rate sheet rows.
+        entries: Vec<RateSheetEntry>,
+    }
+
+    /// Keeps the exchange rates up to date with a provider.
+    ///
+    /// Fetched rates are cached for a short while and saved to a cache file. If a fetch fails, the last known good rates
+    /// are kept (or loaded from the cache file on startup), and a warning is printed once they become stale.
+    pub struct RateFeed {
+        /// The provider that rates are fetched from.
+        provider: Box<dyn RateProvider>,
+        /// The file that the last known good rates are cached to.
+        cache_path: PathBuf,
+        /// The last rates successfully fetched.
+        last_good: Option<RateSnapshot>,
+        /// When a fetch was last attempted.
+        last_attempted_at: Option<DateTime<Local>>,
+    }
+    impl RateFeed {
+        /// How long fetched rates are reused before fetching again.
+        const CACHE_DURATION: TimeDelta = TimeDelta::minutes(5);
+        /// How old the last known good rates can be before a warning is printed.
+        const STALE_THRESHOLD: TimeDelta = TimeDelta::hours(24);
+        /// Creates a new feed that has not fetched any rates yet.
+        pub fn new(provider: Box<dyn RateProvider>) -> RateFeed {
+            RateFeed {
+                provider,
+                cache_path: config::get().files.rate_cache.clone(),
+                last_good: None,
+                last_attempted_at: None,
+            }
+        }
+
+        /// Fetches and validates the provider's rates, rejecting the whole sheet if any row is invalid or any round trip is
+        /// inconsistent.
+        fn fetch_latest_rates(&self, today: NaiveDate) -> Result<Vec<RateSheetEntry>, Box<dyn error::Error>> {
+            let entries = self.provider.fetch()?;
+            let (latest_quotes, rejected_rows) =
+                currency::validate_rate_sheet(entries.iter().cloned().map(Ok).collect(), today);
+
+            if let Some((row_num, reason)) = rejected_rows.first() {
+                return Err(format!("Row {row_num}: {reason}").into());
+            }
+
+            if let Some(RoundTrip { path: [a, b, c], gain }) = currency::inconsistent_round_trips(
+                &currency::quote_table(&latest_quotes),
+                currency::ROUND_TRIP_TOLERANCE,
+            )
+            .first()
+            {
+                return Err(format!("Round trip {a} -> {b} -> {c} -> {a} gains {:+.3}%", gain * 100.0).into());
+            }
+
+            Ok(entries)
+        }
+
+        /// Records the most recent rate of each currency in a snapshot.
+        fn apply(snapshot: &RateSnapshot, rates: &mut HashMap<&str, f64>, rate_dates: &mut HashMap<&str, NaiveDate>) {
+            let (latest_quotes, _) = currency::validate_rate_sheet(
+                snapshot.entries.iter().cloned().map(Ok).collect(),
+                snapshot.fetched_at.date_naive(),
+            );
+            let latest_rates = currency::php_rates(&latest_quotes, rates);
+
+            for code in CURRENCIES_CODES.iter().skip(1) {
+                if let Some(&(rate, date)) = latest_rates.get(code) {
+                    rates.insert(code, rate);
+                    rate_dates.insert(code, date);
+                }
+            }
+        }
+
+        /// Loads the last known good rates from the cache file, if it was written for the same source.
+        fn load_cached_snapshot(&self) -> Option<RateSnapshot> {
+            let snapshot = serde_json::from_reader::<_, RateSnapshot>(File::open(&self.cache_path).ok()?).ok()?;
+
+            (snapshot.source == self.provider.source()).then_some(snapshot)
+        }
+
+        /// Refreshes the exchange rates from the provider unless the cached rates are still fresh.
+        ///
+        /// On failure, the last known good rates are used instead. A warning is printed if those rates are stale.
+        pub fn refresh(&mut self, rates: &mut HashMap<&str, f64>, rate_dates: &mut HashMap<&str, NaiveDate>) {
+            for msg in self.refresh_at(Local::now(), rates, rate_dates) {
+                println!("{msg}");
+            }
+        }
+
+        /// Refreshes the exchange rates as of a time, returning the messages to print.
+        fn refresh_at(
+            &mut self,
+            now: DateTime<Local>,
+            rates: &mut HashMap<&str, f64>,
+            rate_dates: &mut HashMap<&str, NaiveDate>,
+        ) -> Vec<String> {
+            let mut msgs = Vec::<String>::new();
+
+            if self
+                .last_attempted_at
+                .is_some_and(|t| now - t < RateFeed::CACHE_DURATION)
+            {
+                return msgs;
+            }
+
+            self.last_attempted_at = Some(now);
+
+            match self.fetch_latest_rates(now.date_naive()) {
+                Ok(entries) => {
+                    let snapshot = RateSnapshot {
+                        source: self.provider.source().to_string(),
+                        fetched_at: now,
+                        entries,
+                    };
+
+                    RateFeed::apply(&snapshot, rates, rate_dates);
+
+                    if let Err(err) = File::create(&self.cache_path)
+                        .map_err(Box::<dyn error::Error>::from)
+                        .and_then(|f| Ok(serde_json::to_writer_pretty(f, &snapshot)?))
+                    {
+                        msgs.push(format!("Warning: Failed to cache the exchange rates: {err}"));
+                    }
+
+                    self.last_good = Some(snapshot);
+                }
+                Err(err) => {
+                    msgs.push(format!(
+                        "Warning: Failed to fetch exchange rates from {}: {err}",
+                        self.provider.source()
+                    ));
+
+                    if self.last_good.is_none() {
+                        self.last_good = self.load_cached_snapshot();
+
+                        if let Some(snapshot) = &self.last_good {
+                            RateFeed::apply(snapshot, rates, rate_dates);
+                        }
+                    }
+
+                    msgs.push(match &self.last_good {
+                        Some(snapshot) => format!(
+                            "Using the last known good exchange rates from {}.",
+                            snapshot.fetched_at.format("%Y-%m-%d %H:%M")
+                        ),
+                        None => String::from("Using the current exchange rates."),
+                    });
+                }
+            }
+
+            if let Some(snapshot) = &self.last_good
+                && now - snapshot.fetched_at > RateFeed::STALE_THRESHOLD
+            {
+                msgs.push(format!(
+                    "Warning: Exchange rates are stale (last fetched {} hours ago)!",
+                    (now - snapshot.fetched_at).num_hours()
+                ));
+            }
+
+            msgs
+        }
+    }
+
+    /// A stand-in for a real rate endpoint, which serves a JSON rate sheet file over HTTP on the local machine.
+    ///
+    /// The file is re-read on every request to `/rates` so that its rates can be edited while the server is running.
+    /// Any other path responds with a 404, which can be used to simulate an outage.
+    pub struct MockRateServer {
+        /// The listener that requests are accepted from.
+        listener: TcpListener,
+        /// The path of the rate sheet file.
+        path: String,
+    }
+    impl MockRateServer {
+        /// Binds a server for a rate sheet file to a port on the local machine, where port 0 picks any free port.
+        pub fn bind(path: &str, port: u16) -> Result<MockRateServer, Box<dyn error::Error>> {
+            Ok(MockRateServer {
+                listener: TcpListener::bind(("127.0.0.1", port))?,
+                path: path.to_string(),
+            })
+        }
+
+        /// Gets the URL that the rates are served at.
+        pub fn url(&self) -> Result<String, Box<dyn error::Error>> {
+            Ok(format!("http://{}/rates", self.listener.local_addr()?))
+        }
+
+        /// Serves requests until a number of them were accepted, or forever if no number is given.
+        ///
+        /// A request that fails is reported without stopping the server.
+        pub fn serve(&self, max_request_cnt: Option<usize>) {
+            for stream in self.listener.incoming().take(max_request_cnt.unwrap_or(usize::MAX)) {
+                if let Err(err) = stream.map_err(Box::from).and_then(|s| self.respond(s)) {
+                    println!("Failed to serve a request: {err}");
+                }
+            }
+        }
+
+        /// Reads a request and writes its response.
+        fn respond(&self, mut stream: TcpStream) -> Result<(), Box<dyn error::Error>> {
+            // A client that never finishes its request must not hold up the others.
+            stream.set_read_timeout(Some(HttpRateProvider::TIMEOUT))?;
+
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(&stream);
+
+            reader.read_line(&mut request_line)?;
+
+            for line in reader.lines() {
+                if line?.is_empty() {
+                    break;
+                }
+            }
+
+            let (status, body) = match request_line.split_whitespace().nth(1) {
+                Some("/rates") => match fs::read_to_string(&self.path) {
+                    Ok(body) => ("200 OK", body),
+                    Err(err) => ("500 Internal Server Error", format!("{{\"error\":\"{err}\"}}")),
+                },
+                _ => ("404 Not Found", String::from("{\"error\":\"Not Found\"}")),
+            };
+
+            println!("{} -> {status}", request_line.trim());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )?;
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::{cell::Cell, env, process, thread};
+
+        /// A provider whose fetches succeed or fail on demand.
+        struct StubRateProvider {
+            /// The rows that a successful fetch returns.
+            entries: Vec<RateSheetEntry>,
+            /// Whether fetches fail.
+            is_down: Cell<bool>,
+        }
+        impl RateProvider for StubRateProvider {
+            fn source(&self) -> &str {
+                "stub"
+            }
+
+            fn fetch(&self) -> Result<Vec<RateSheetEntry>, Box<dyn error::Error>> {
+                if self.is_down.get() {
+                    Err("Rate endpoint is down!".into())
+                } else {
+                    Ok(self.entries.clone())
+                }
+            }
+        }
+
+        /// Gets a path in the temporary directory that is unique to the test process.
+        fn temp_path(name: &str) -> PathBuf {
+            env::temp_dir().join(format!("mco1-{}-{name}", process::id()))
+        }
+
+        fn usd_entry(rate: f64) -> RateSheetEntry {
+            RateSheetEntry {
+                code: String::from("USD"),
+                base: String::from("PHP"),
+                rate,
+                effective_date: NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            }
+        }
+
+        #[test]
+        fn http_provider_fetches_from_mock_server() {
+            let path = temp_path("mock-rates.json");
+
+            fs::write(&path, serde_json::to_string(&[usd_entry(56.0)]).unwrap()).unwrap();
+
+            let server = MockRateServer::bind(path.to_str().unwrap(), 0).unwrap();
+            let url = server.url().unwrap();
+            let addr = url.trim_start_matches("http://").trim_end_matches("/rates").to_string();
+            let handle = thread::spawn(move || server.serve(Some(3)));
+
+            // A client that hangs up without a request does not stop the server.
+            drop(TcpStream::connect(&addr).unwrap());
+
+            let outage = HttpRateProvider {
+                url: url.replace("/rates", "/outage"),
+            };
+            let provider = HttpRateProvider { url };
+
+            assert!(outage.fetch().is_err());
+
+            let entries = provider.fetch().unwrap();
+
+            handle.join().unwrap();
+            fs::remove_file(&path).unwrap();
+
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].code, "USD");
+            assert_eq!(entries[0].rate, 56.0);
+        }
+
+        #[test]
+        fn feed_falls_back_to_cache_and_warns_when_stale() {
+            let cache_path = temp_path("rate-cache.json");
+            let now = Local::now();
+            let snapshot = RateSnapshot {
+                source: String::from("stub"),
+                fetched_at: now - TimeDelta::hours(48),
+                entries: vec![usd_entry(57.0)],
+            };
+
+            fs::write(&cache_path, serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+            let mut feed = RateFeed::new(Box::new(StubRateProvider {
+                entries: Vec::new(),
+                is_down: Cell::new(true),
+            }));
+            let mut rates = HashMap::from([("USD", 1.0)]);
+            let mut rate_dates = HashMap::new();
+
+            feed.cache_path = cache_path.clone();
+
+            let msgs = feed.refresh_at(now, &mut rates, &mut rate_dates);
+
+            fs::remove_file(&cache_path).unwrap();
+
+            assert_eq!(rates["USD"], 57.0);
+            assert!(msgs[0].starts_with("Warning: Failed to fetch exchange rates from stub"));
+            assert!(msgs[1].starts_with("Using the last known good exchange rates"));
+            assert_eq!(
+                msgs[2],
+                "Warning: Exchange rates are stale (last fetched 48 hours ago)!"
+            );
+        }
+
+        #[test]
+        fn feed_keeps_last_good_rates_when_fetch_fails() {
+            let cache_path = temp_path("rate-cache-last-good.json");
+            let provider = StubRateProvider {
+                entries: vec![usd_entry(58.0)],
+                is_down: Cell::new(false),
+            };
+            let mut feed = RateFeed::new(Box::new(provider));
+            let mut rates = HashMap::from([("USD", 1.0)]);
+            let mut rate_dates = HashMap::new();
+            let now = Local::now();
+
+            feed.cache_path = cache_path.clone();
+
+            assert!(feed.refresh_at(now, &mut rates, &mut rate_dates).is_empty());
+            assert_eq!(rates["USD"], 58.0);
+
+            // Fresh rates are reused without fetching again.
+            assert!(
+                feed.refresh_at(now + TimeDelta::minutes(1), &mut rates, &mut rate_dates)
+                    .is_empty()
+            );
+
+            feed.provider = Box::new(StubRateProvider {
+                entries: Vec::new(),
+                is_down: Cell::new(true),
+            });
+            rates.insert("USD", 1.0);
+
+            let msgs = feed.refresh_at(now + TimeDelta::hours(25), &mut rates, &mut rate_dates);
+
+            fs::remove_file(&cache_path).unwrap();
+
+            assert_eq!(rates["USD"], 1.0);
+            assert_eq!(msgs.len(), 3);
+            assert_eq!(
+                msgs[2],
+                "Warning: Exchange rates are stale (last fetched 25 hours ago)!"
+            );
+        }
+    }
+}
+
+mod fx_position {
+    use serde::{Deserialize, Serialize};
+    use std::{collections::BTreeMap, collections::HashMap, sync::Mutex};
+
+    /// The bank's holdings of each foreign currency.
+    static BOOK: Mutex<PositionBook> = Mutex::new(PositionBook {
+        positions: BTreeMap::new(),
+    });
+
+    /// The bank's holding of a foreign currency, valued in PHP.
+    #[derive(Clone, Default, Deserialize, Serialize)]
+    pub struct Position {
+        /// The units held, which is negative when more was paid out than received.
+        pub quantity: f64,
+        /// The average PHP paid per unit for the current holding.
+        pub average_cost: f64,
+        /// The profit (or loss, if negative) locked in by reducing the holding.
+        pub realized_pnl: f64,
+    }
+    impl Position {
+        /// Trades units of the currency at a price in PHP per unit, with positive units being bought and negative units
+        /// being sold.
+        fn trade(&mut self, units: f64, price: f64) {
+            let quantity = self.quantity + units;
+
+            if self.quantity == 0.0 || self.quantity.signum() == units.signum() {
+                self.average_cost = (self.quantity * self.average_cost + units * price) / quantity;
+            } else {
+                let closed_units = units.abs().min(self.quantity.abs());
+
+                self.realized_pnl += closed_units * (price - self.average_cost) * self.quantity.signum();
+
+                if quantity == 0.0 {
+                    self.average_cost = 0.0;
+                } else if quantity.signum() != self.quantity.signum() {
+                    self.average_cost = price;
+                }
+            }
+
+            self.quantity = quantity;
+        }
+
+        /// Computes the profit (or loss, if negative) of the holding if it were closed at a rate in PHP per unit.
+        pub fn unrealized_pnl(&self, rate: f64) -> f64 {
+            self.quantity * (rate - self.average_cost)
+        }
+    }
+
+    /// The positions of every foreign currency that the bank has traded, keyed by currency code.
+    #[derive(Clone, Default, Deserialize, Serialize)]
+    pub struct PositionBook {
+        /// The position of each traded currency.
+        positions: BTreeMap<String, Position>,
+    }
+
+    /// Books a conversion leg in a foreign currency, with positive units being received by the bank and negative units
+    /// being paid out, for an amount in PHP.
+    pub fn book(code: &str, units: f64, php_amount: f64) {
+        if code == "PHP" || units == 0.0 {
+            return;
+        }
+
+        let mut book = BOOK.lock().unwrap_or_else(|e| e.into_inner());
+
+        book.positions
+            .entry(code.to_string())
+            .or_default()
+            .trade(units, php_amount.abs() / units.abs());
+    }
+
+    /// Copies the current positions, so that they can be saved or restored.
+    pub fn snapshot() -> PositionBook {
+        BOOK.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replaces the current positions with a snapshot of them.
+    pub fn restore(book: PositionBook) {
+        *BOOK.lock().unwrap_or_else(|e| e.into_inner()) = book;
+    }
+
+    /// Prints every position, revalued against the current exchange rates.
+    pub fn print_positions(rates: &HashMap<&str, f64>) {
+        let book = snapshot();
+
+        if book.positions.is_empty() {
+            println!("No foreign currencies have been traded!");
+
+            return;
+        }
+
+        println!(
+            "{:<8} | {:<14} | {:<12} | {:<12} | {:<14} | {:<14} |",
+            "Currency", "Position", "Avg. Cost", "Rate", "Realized P&L", "Unrealized P&L"
+        );
+
+        let mut total_realized_pnl = 0.0;
+        let mut total_unrealized_pnl = 0.0;
+
+        for (code, position) in &book.positions {
+            let rate = rates.get(code.as_str()).copied().unwrap_or(0.0);
+            let unrealized_pnl = position.unrealized_pnl(rate);
+
+            total_realized_pnl += position.realized_pnl;
+            total_unrealized_pnl += unrealized_pnl;
+
+            println!(
+                "{code:<8} | {:<14.2} | {:<12.4} | {rate:<12.4} | {:<14.2} | {unrealized_pnl:<14.2} |",
+                position.quantity, position.average_cost, position.realized_pnl
+            );
+        }
+
+        println!();
+
+        println!("Total Realized P&L (PHP): {total_realized_pnl:.2}");
+        println!("Total Unrealized P&L (PHP): {total_unrealized_pnl:.2}");
+    }
+}
+
+mod account_number {
+    /// The number that account sequence numbers are offset by, so that every account number has the same length.
+    const SEQUENCE_OFFSET: usize = 100_000_000;
+
+    /// Computes the [Luhn](https://en.wikipedia.org/wiki/Luhn_algorithm) check digit of a string of digits.
+    fn check_digit(payload: &str) -> u32 {
+        let sum = payload
+            .chars()
+            .rev()
+            .filter_map(|c| c.to_digit(10))
+            .enumerate()
+            .map(|(i, d)| {
+                if i % 2 == 0 {
+                    if d * 2 > 9 { d * 2 - 9 } else { d * 2 }
+                } else {
+                    d
+                }
+            })
+            .sum::<u32>();
+
+        (10 - sum % 10) % 10
+    }
+
+    /// Generates the account number of the account registered with a sequence number.
+    ///
+    /// The number is the offset sequence number followed by its check digit.
+    pub fn generate(seq: usize) -> String {
+        let payload = (SEQUENCE_OFFSET + seq).to_string();
+
+        format!("{payload}{}", check_digit(&payload))
+    }
+
+    /// Checks whether a string is a well-formed account number with a matching check digit.
+    pub fn is_valid(number: &str) -> bool {
+        let Some((payload, last)) = number.split_at_checked(number.len().saturating_sub(1)) else {
+            return false;
+        };
+
+        !payload.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+            && last.parse::<u32>().is_ok_and(|d| d == check_digit(payload))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn check_digit_matches_known_vectors() {
+            assert_eq!(check_digit("7992739871"), 3);
+            assert_eq!(check_digit("453914880343646"), 7);
+            assert_eq!(check_digit("0"), 0);
+            assert_eq!(check_digit("1"), 8);
+        }
+
+        #[test]
+        fn is_valid_accepts_only_matching_check_digits() {
+            assert!(is_valid("79927398713"));
+            assert!(is_valid("4539148803436467"));
+            assert!(!is_valid("79927398710"));
+            assert!(!is_valid("7992739871a"));
+            assert!(!is_valid("3"));
+            assert!(!is_valid(""));
+        }
+
+        #[test]
+        fn generated_numbers_are_valid() {
+            for seq in [0, 1, 42, 99_999_999] {
+                assert!(is_valid(&generate(seq)));
+            }
+
+            assert_eq!(generate(1), "1000000016");
+        }
+    }
+}
+
+mod loan {
+    use crate::{
+        clock,
+        io_util::{print_ordered_list, prompt_number, prompt_option},
+    };
+    use chrono::{Months, NaiveDate, TimeDelta};
+    use serde::{Deserialize, Serialize};
+
+    /// The titles of the methods that a loan can be repaid with.
+    pub const REPAYMENT_METHOD_TITLES: [&str; 2] = ["Equal Installments", "Equal Principal"];
+
+    /// The percentage of an installment's payment that is charged as a penalty when it is paid late.
+    pub const LATE_PAYMENT_PENALTY_RATE: f64 = 0.05;
+    /// The number of days after an installment's due date that it can still be paid without a penalty.
+    pub const GRACE_PERIOD_DAYS: i64 = 5;
+    /// The longest term that a loan can be granted for, in months.
+    pub const MAX_TERM_MONTHS: u32 = 600;
+
+    /// The methods that a loan can be repaid with.
+    #[derive(Clone, Copy, PartialEq, Deserialize, Serialize)]
+    pub enum RepaymentMethod {
+        /// Every installment has the same payment, with its share of principal growing as the interest shrinks.
+        EqualInstallments,
+        /// Every installment repays the same principal, so its payment shrinks along with the interest.
+        EqualPrincipal,
+    }
+
+    /// A single monthly payment in a loan's amortization schedule.
+    pub struct Installment {
+        /// The position of the installment in the schedule, starting from one.
+        pub number: u32,
+        /// The date that the installment must be paid by.
+        pub due_date: NaiveDate,
+        /// The total amount to pay.
+        pub payment: f64,
+        /// The part of the payment that repays the principal.
+        pub principal: f64,
+        /// The part of the payment that pays the interest.
+        pub interest: f64,
+        /// The principal still owed after the installment is paid.
+        pub balance: f64,
+    }
+
+    /// The breakdown of the amount needed to pay off a loan on a given date.
+    pub struct PayoffQuote {
+        /// The principal still owed.
+        pub principal: f64,
+        /// The interest accrued since the last paid installment's due date.
+        pub interest: f64,
+        /// The penalties of the installments that are past their grace period.
+        pub penalties: f64,
+    }
+    impl PayoffQuote {
+        /// Gets the total amount needed to pay off the loan.
+        pub fn total(&self) -> f64 {
+            self.principal + self.interest + self.penalties
+        }
+    }
+
+    /// A loan and the progress of its repayment.
+    #[derive(Clone, Copy, PartialEq, Deserialize, Serialize)]
+    pub struct Loan {
+        /// The amount borrowed.
+        pub principal: f64,
+        /// The annual interest rate percentage.
+        pub annual_interest_rate: f64,
+        /// The number of monthly installments.
+        pub term_months: u32,
+        /// How the loan is repaid.
+        pub method: RepaymentMethod,
+        /// The date that the loan was granted, from which installments fall due monthly.
+        pub start_date: NaiveDate,
+        /// The number of installments that were already paid.
+        pub paid_installment_cnt: u32,
+    }
+    impl Loan {
+        /// Prompts a CLI user to input the terms of a new loan starting today.
+        pub fn prompt_terms() -> Option<Loan> {
+            let principal = prompt_number(
+                "Principal: ",
+                f64::MIN_POSITIVE..=f64::MAX,
+                "Principal must be a positive floating point number!",
+            )?;
+            let annual_interest_rate = prompt_number(
+                "Annual Interest Rate (%): ",
+                0.0..=f64::MAX,
+                "Interest rate must be a non-negative floating point number!",
+            )? / 100.0;
+            let term_months = prompt_number(
+                "Term (Months): ",
+                1..=MAX_TERM_MONTHS,
+                &format!("Term must be a whole number (integer) from 1 to {MAX_TERM_MONTHS}!"),
+            )?;
+
+            println!();
+
+            println!("Repayment Method Options:");
+            print_ordered_list(&REPAYMENT_METHOD_TITLES);
+
+            println!();
+
+            let method = match prompt_option(
+                "Repayment Method: ",
+                REPAYMENT_METHOD_TITLES.len(),
+                "No repayment method with this ID exists!",
+            )? {
+                0 => RepaymentMethod::EqualInstallments,
+                _ => RepaymentMethod::EqualPrincipal,
+            };
+
+            Some(Loan {
+                principal,
+                annual_interest_rate,
+                term_months,
+                method,
+                start_date: clock::today(),
+                paid_installment_cnt: 0,
+            })
+        }
+
+        /// Computes every installment of the loan.
+        ///
+        /// Amounts are rounded to centavos, with the last installment repaying whatever principal is left.
+        pub fn amortization_schedule(&self) -> Vec<Installment> {
+            let monthly_rate = self.annual_interest_rate / 12.0;
+            let term_months = self.term_months as f64;
+            let installment_payment = if monthly_rate == 0.0 {
+                self.principal / term_months
+            } else {
+                self.principal * monthly_rate / (1.0 - (1.0 + monthly_rate).powf(-term_months))
+            };
+            let mut balance = self.principal;
+            let mut installments = Vec::<Installment>::new();
+
+            for number in 1..=self.term_months {
+                let interest = (balance * monthly_rate * 100.0).round() / 100.0;
+                let principal = if number == self.term_months {
+                    balance
+                } else {
+                    match self.method {
+                        RepaymentMethod::EqualInstallments => {
+                            ((installment_payment - interest) * 100.0).round() / 100.0
+                        }
+                        RepaymentMethod::EqualPrincipal => (self.principal / term_months * 100.0).round() / 100.0,
+                    }
+                };
+
+                balance = ((balance - principal) * 100.0).round() / 100.0;
+
+                installments.push(Installment {
+                    number,
+                    due_date: self.start_date + Months::new(number),
+                    payment: ((principal + interest) * 100.0).round() / 100.0,
+                    principal,
+                    interest,
+                    balance,
+                });
+            }
+
+            installments
+        }
+
+        /// Gets the next installment to be paid, if the loan has not been paid off.
+        pub fn next_installment(&self) -> Option<Installment> {
+            self.amortization_schedule()
+                .into_iter()
+                .nth(self.paid_installment_cnt as usize)
+        }
+
+        /// Checks whether an installment is past its grace period on a date.
+        pub fn is_overdue(installment: &Installment, date: NaiveDate) -> bool {
+            date > installment.due_date + TimeDelta::days(GRACE_PERIOD_DAYS)
+        }
+
+        /// Computes the penalty for paying an installment on a date, which is zero within its grace period.
+        pub fn late_penalty(installment: &Installment, date: NaiveDate) -> f64 {
+            if Loan::is_overdue(installment, date) {
+                (installment.payment * LATE_PAYMENT_PENALTY_RATE * 100.0).round() / 100.0
+            } else {
+                0.0
+            }
+        }
+
+        /// Computes the amount needed to pay off the loan on a date.
+        ///
+        /// Interest accrues daily on the principal still owed since the last paid installment's due date (or the start
+        /// date), and every unpaid installment past its grace period adds its late penalty.
+        pub fn payoff_quote(&self, date: NaiveDate) -> PayoffQuote {
+            let schedule = self.amortization_schedule();
+            let paid_cnt = self.paid_installment_cnt as usize;
+            let (principal, accrual_date) = match paid_cnt.checked_sub(1).map(|i| &schedule[i]) {
+                Some(installment) => (installment.balance, installment.due_date),
+                None => (self.principal, self.start_date),
+            };
+            let accrual_days = (date - accrual_date).num_days().max(0) as f64;
+
+            PayoffQuote {
+                principal,
+                interest: (principal * self.annual_interest_rate * accrual_days / 365.0 * 100.0).round() / 100.0,
+                penalties: schedule
+                    .iter()
+                    .skip(paid_cnt)
+                    .map(|i| Loan::late_penalty(i, date))
+                    .sum::<f64>(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn loan(annual_interest_rate: f64, term_months: u32, method: RepaymentMethod) -> Loan {
+            Loan {
+                principal: 100_000.0,
+                annual_interest_rate,
+                term_months,
+                method,
+                start_date: NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(),
+                paid_installment_cnt: 0,
+            }
+        }
+
+        #[test]
+        fn installments_repay_the_principal() {
+            for method in [RepaymentMethod::EqualInstallments, RepaymentMethod::EqualPrincipal] {
+                for (annual_interest_rate, term_months) in [(0.0, 7), (0.06, 12), (0.125, 360), (0.2, 1)] {
+                    let schedule = loan(annual_interest_rate, term_months, method).amortization_schedule();
+                    let principal_sum = schedule.iter().map(|i| i.principal).sum::<f64>();
+
+                    assert_eq!(schedule.len(), term_months as usize);
+                    assert!((principal_sum - 100_000.0).abs() < 0.005);
+                    assert_eq!(schedule.last().unwrap().balance, 0.0);
+                }
+            }
+        }
+
+        #[test]
+        fn equal_installments_have_equal_payments() {
+            let schedule = loan(0.06, 12, RepaymentMethod::EqualInstallments).amortization_schedule();
+
+            assert_eq!(schedule[0].payment, 8606.64);
+            assert!(schedule[..11].iter().all(|i| i.payment == 8606.64));
+            // The last installment absorbs the rounding of the others.
+            assert_eq!(schedule[11].payment, 8606.69);
+            assert_eq!(schedule[1].due_date, NaiveDate::from_ymd_opt(2020, 3, 31).unwrap());
+        }
+    }
+}
+
+mod accounting_export {
+    use crate::{
+        Account, AccountType, Transaction, TransactionKind, clock, currency::CURRENCIES_CODES, io_util::prompt_file,
+    };
+    use std::{error, fmt::Write as _, fs, path::Path};
+
+    /// The identifier of the bank in OFX files.
+    const OFX_BANK_ID: &str = "MCO1BANK";
+
+    /// Pairs each of an account's transactions with how much it changed the balance by.
+    pub fn signed_transactions(account: &Account) -> impl Iterator<Item = (&Transaction, f64)> {
+        account.transactions.iter().scan(0.0, |prev_balance, transaction| {
+            let change = transaction.balance - *prev_balance;
+
+            *prev_balance = transaction.balance;
+
+            Some((transaction, change))
+        })
+    }
+
+    /// Gets the ISO 4217 code of an account's currency.
+    fn currency_code(account: &Account) -> Result<&'static str, Box<dyn error::Error>> {
+        CURRENCIES_CODES
+            .iter()
+            .find(|&&c| c == account.currency)
+            .copied()
+            .ok_or_else(|| {
+                format!(
+                    "Account currency \"{}\" is not an exchangeable currency!",
+                    account.currency
+                )
+                .into()
+            })
+    }
+
+    /// Gets the OFX transaction type of a transaction.
+    fn ofx_transaction_type(kind: TransactionKind, change: f64) -> &'static str {
+        match kind {
+            TransactionKind::Interest => "INT",
+            TransactionKind::Penalty
+            | TransactionKind::OverdraftFee
+            | TransactionKind::OverdraftInterest
+            | TransactionKind::LoanInterest
+            | TransactionKind::LatePaymentPenalty => "FEE",
+            TransactionKind::TransferIn | TransactionKind::TransferOut => "XFER",
+            TransactionKind::LoanPayment => "PAYMENT",
+            _ if change < 0.0 => "DEBIT",
+            _ => "CREDIT",
+        }
+    }
+
+    /// Renders an account's transactions as an OFX 2.2 bank statement.
+    pub fn render_ofx(account: &Account) -> Result<String, Box<dyn error::Error>> {
+        let code = currency_code(account)?;
+        let now = clock::now().format("%Y%m%d%H%M%S").to_string();
+        let account_type = match account.kind {
+            AccountType::Savings => "SAVINGS",
+            AccountType::Checking => "CHECKING",
+            AccountType::TimeDeposit { .. } => "CD",
+            AccountType::Loan(_) => "CREDITLINE",
+        };
+        let start_date = account
+            .transactions
+            .first()
+            .map_or(now.clone(), |t| t.timestamp.format("%Y%m%d%H%M%S").to_string());
+        let mut ofx = String::new();
+
+        let _ = writeln!(ofx, "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>");
+        let _ = writeln!(
+            ofx,
+            "<?OFX OFXHEADER=\"200\" VERSION=\"220\" SECURITY=\"NONE\" OLDFILEUID=\"NONE\" NEWFILEUID=\"NONE\"?>"
+        );
+        let _ = writeln!(ofx, "<OFX>");
+        let _ = writeln!(ofx, "<SIGNONMSGSRSV1><SONRS>");
+        let _ = writeln!(ofx, "<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>");
+        let _ = writeln!(ofx, "<DTSERVER>{now}</DTSERVER><LANGUAGE>ENG</LANGUAGE>");
+        let _ = writeln!(ofx, "</SONRS></SIGNONMSGSRSV1>");
+        let _ = writeln!(ofx, "<BANKMSGSRSV1><STMTTRNRS>");
+        let _ = writeln!(ofx, "<TRNUID>{}</TRNUID>", account.number);
+        let _ = writeln!(ofx, "<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>");
+        let _ = writeln!(ofx, "<STMTRS>");
+        let _ = writeln!(ofx, "<CURDEF>{code}</CURDEF>");
+        let _ = writeln!(
+            ofx,
+            "<BANKACCTFROM><BANKID>{OFX_BANK_ID}</BANKID><ACCTID>{}</ACCTID><ACCTTYPE>{account_type}</ACCTTYPE></BANKACCTFROM>",
+            account.number
+        );
+        let _ = writeln!(ofx, "<BANKTRANLIST>");
+        let _ = writeln!(ofx, "<DTSTART>{start_date}</DTSTART><DTEND>{now}</DTEND>");
+
+        for (transaction, change) in signed_transactions(account) {
+            let _ = writeln!(ofx, "<STMTTRN>");
+            let _ = writeln!(
+                ofx,
+                "<TRNTYPE>{}</TRNTYPE>",
+                ofx_transaction_type(transaction.kind, change)
+            );
+            let _ = writeln!(
+                ofx,
+                "<DTPOSTED>{}</DTPOSTED>",
+                transaction.timestamp.format("%Y%m%d%H%M%S")
+            );
+            let _ = writeln!(ofx, "<TRNAMT>{change:.2}</TRNAMT>");
+            let _ = writeln!(ofx, "<FITID>{}</FITID>", transaction.reference);
+            let _ = writeln!(ofx, "<NAME>{}</NAME>", transaction.kind);
+            let _ = writeln!(ofx, "<CURRENCY><CURRATE>1</CURRATE><CURSYM>{code}</CURSYM></CURRENCY>");
+            let _ = writeln!(ofx, "</STMTTRN>");
+        }
+
+        let _ = writeln!(ofx, "</BANKTRANLIST>");
+        let _ = writeln!(
+            ofx,
+            "<LEDGERBAL><BALAMT>{:.2}</BALAMT><DTASOF>{now}</DTASOF></LEDGERBAL>",
+            account.balance
+        );
+        let _ = writeln!(
+            ofx,
+            "<AVAILBAL><BALAMT>{:.2}</BALAMT><DTASOF>{now}</DTASOF></AVAILBAL>",
+            account.available_balance()
+        );
+        let _ = writeln!(ofx, "</STMTRS>");
+        let _ = writeln!(ofx, "</STMTTRNRS></BANKMSGSRSV1>");
+        let _ = writeln!(ofx, "</OFX>");
+
+        Ok(ofx)
+    }
+
+    /// Renders an account's transactions as a QIF bank account.
+    ///
+    /// QIF has no field for currencies, so the currency code is noted in each transaction's memo.
+    pub fn render_qif(account: &Account) -> Result<String, Box<dyn error::Error>> {
+        let code = currency_code(account)?;
+        let account_type = match account.kind {
+            AccountType::Loan(_) => "Oth L",
+            _ => "Bank",
+        };
+        let mut qif = String::new();
+
+        let _ = writeln!(qif, "!Account");
+        let _ = writeln!(qif, "N{}", account.number);
+        let _ = writeln!(qif, "T{account_type}");
+        let _ = writeln!(qif, "D{} ({})", account.name, account.kind);
+        let _ = writeln!(qif, "^");
+        let _ = writeln!(qif, "!Type:{account_type}");
+
+        for (transaction, change) in signed_transactions(account) {
+            let _ = writeln!(qif, "D{}", transaction.timestamp.format("%m/%d/%Y"));
+            let _ = writeln!(qif, "T{change:.2}");
+            let _ = writeln!(qif, "N{}", transaction.reference);
+            let _ = writeln!(qif, "P{}", transaction.kind);
+            let _ = writeln!(qif, "M{} ({code})", transaction.kind);
+            let _ = writeln!(qif, "^");
+        }
+
+        Ok(qif)
+    }
+
+    /// Writes an account's transactions to a file, rendered based on the file's extension.
+    pub fn write_activity(path: &str, account: &Account) -> Result<(), Box<dyn error::Error>> {
+        let contents = match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("ofx") => render_ofx(account)?,
+            Some("qif") => render_qif(account)?,
+            _ => return Err("Activity file must be a .ofx or .qif file!".into()),
+        };
+
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    /// Exports an account's transactions for accounting software.
+    ///
+    /// The user is prompted to input the file to export to.
+    pub fn export_activity(account: &Account) {
+        let Some(path) = prompt_file("Activity File (.ofx/.qif): ", &["ofx", "qif"]) else {
+            return;
+        };
+
+        println!();
+
+        match write_activity(&path, account) {
+            Ok(()) => println!("Exported {} transactions to {path}", account.transactions.len()),
+            Err(err) => println!("Failed to write the activity file: {err}"),
+        }
+    }
+}
+
+mod reconciliation {
+    use crate::{
+        Account, Transaction, TransactionKind, accounting_export,
+        io_util::{confirm, prompt_file},
+    };
+    use chrono::NaiveDate;
+    use serde::Deserialize;
+    use std::error;
+
+    /// How far apart two amounts can be while still being treated as equal.
+    const AMOUNT_TOLERANCE: f64 = 0.005;
+
+    /// A line of an external statement, as read from a CSV file.
+    #[derive(Deserialize)]
+    pub struct ExternalLine {
+        /// The date of the transaction.
+        pub date: NaiveDate,
+        /// The reference number of the transaction, if the statement has it.
+        #[serde(default)]
+        pub reference: String,
+        /// What the transaction was.
+        #[serde(default)]
+        pub description: String,
+        /// How much the transaction changed the balance by, negative for debits.
+        pub amount: f64,
+    }
+
+    /// The result of matching an external statement against an account's transactions.
+    pub struct Reconciliation<'a> {
+        /// The external lines paired with the transactions that they matched.
+        pub matched: Vec<(ExternalLine, &'a Transaction)>,
+        /// The external lines that no transaction matched.
+        pub missing: Vec<ExternalLine>,
+        /// The transactions within the statement's period that no external line matched.
+        pub unexpected: Vec<(&'a Transaction, f64)>,
+    }
+
+    /// Reads an external statement from a CSV file with the `date`, `reference`, `description` and `amount` columns.
+    pub fn read_external_statement(path: &str) -> Result<Vec<ExternalLine>, Box<dyn error::Error>> {
+        let mut fr = csv::ReaderBuilder::new().trim(csv::Trim::All).from_path(path)?;
+        let mut lines = Vec::<ExternalLine>::new();
+
+        for line in fr.deserialize() {
+            lines.push(line?);
+        }
+
+        Ok(lines)
+    }
+
+    /// Matches the lines of an external statement against an account's transactions.
+    ///
+    /// Lines with a reference number are matched to the transaction with that number first. The rest are matched to a
+    /// transaction on the same date with the same amount.
+    pub fn reconcile(account: &Account, lines: Vec<ExternalLine>) -> Reconciliation<'_> {
+        let (Some(start_date), Some(end_date)) =
+            (lines.iter().map(|l| l.date).min(), lines.iter().map(|l| l.date).max())
+        else {
+            return Reconciliation {
+                matched: Vec::new(),
+                missing: Vec::new(),
+                unexpected: Vec::new(),
+            };
+        };
+        let mut candidates = accounting_export::signed_transactions(account)
+            .filter(|(t, _)| (start_date..=end_date).contains(&t.timestamp.date_naive()))
+            .map(Some)
+            .collect::<Vec<Option<(&Transaction, f64)>>>();
+        let mut matched = Vec::<(ExternalLine, &Transaction)>::new();
+        let mut unmatched_lines = Vec::<ExternalLine>::new();
+
+        for line in lines {
+            let by_reference = candidates
+                .iter()
+                .position(|c| c.is_some_and(|(t, _)| !line.reference.is_empty() && t.reference == line.reference));
+
+            match by_reference.and_then(|i| candidates[i].take()) {
+                Some((transaction, _)) => matched.push((line, transaction)),
+                None => unmatched_lines.push(line),
+            }
+        }
+
+        let mut missing = Vec::<ExternalLine>::new();
+
+        for line in unmatched_lines {
+            let by_date_and_amount = candidates.iter().position(|c| {
+                c.is_some_and(|(t, change)| {
+                    t.timestamp.date_naive() == line.date && (change - line.amount).abs() < AMOUNT_TOLERANCE
+                })
+            });
+
+            match by_date_and_amount.and_then(|i| candidates[i].take()) {
+                Some((transaction, _)) => matched.push((line, transaction)),
+                None => missing.push(line),
+            }
+        }
+
+        Reconciliation {
+            matched,
+            missing,
+            unexpected: candidates.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Reconciles an account against an external statement and posts adjustments for the discrepancies.
+    ///
+    /// The user is prompted to input the statement file, then whether to post an adjustment entry for each external
+    /// line missing from the account and each unexpected transaction of the account.
+    pub fn reconcile_account(account: &mut Account) {
+        let Some(path) = prompt_file("External Statement File (.csv): ", &["csv"]) else {
+            return;
+        };
+
+        println!();
+
+        let lines = match read_external_statement(&path) {
+            Ok(lines) => lines,
+            Err(err) => {
+                println!("Failed to read the external statement: {err}");
+
+                return;
+            }
+        };
+
+        let reconciliation = reconcile(account, lines);
+
+        println!("Matched: {}", reconciliation.matched.len());
+
+        for (line, transaction) in &reconciliation.matched {
+            println!(
+                "  {} | {} | {:.2} <-> {}",
+                line.date, line.description, line.amount, transaction.reference
+            );
+        }
+
+        println!("Missing From Account: {}", reconciliation.missing.len());
+
+        for line in &reconciliation.missing {
+            println!(
+                "  {} | {} | {} | {:.2}",
+                line.date, line.reference, line.description, line.amount
+            );
+        }
+
+        println!("Unexpected In Account: {}", reconciliation.unexpected.len());
+
+        for (transaction, change) in &reconciliation.unexpected {
+            println!(
+                "  {} | {} | {} | {change:.2}",
+                transaction.timestamp.date_naive(),
+                transaction.reference,
+                transaction.kind
+            );
+        }
+
+        // The adjustments are collected first as the reconciliation borrows the account's transactions.
+        let adjustments = reconciliation
+            .missing
+            .iter()
+            .map(|l| (format!("{} {} {:.2}", l.date, l.description, l.amount), l.amount))
+            .chain(
+                reconciliation
+                    .unexpected
+                    .iter()
+                    .map(|(t, change)| (format!("{} {} {change:.2}", t.reference, t.kind), -change)),
+            )
+            .collect::<Vec<(String, f64)>>();
+
+        if adjustments.is_empty() {
+            println!();
+
+            println!("The account matches the external statement.");
+
+            return;
+        }
+
+        for (description, amount) in adjustments {
+            println!();
+
+            let Some(is_posting) = confirm(&format!(
+                "Post an adjustment of {amount:.2} for \"{description}\"? (Y/N): "
+            )) else {
+                break;
+            };
+
+            if is_posting {
+                account.balance += amount;
+
+                let reference = account.record_transaction(TransactionKind::Adjustment, amount);
+
+                println!("Posted Adjustment: {reference} (Updated Balance: {})", account.balance);
+            }
+        }
+    }
+}
+
+mod savings_goal {
+    use crate::{
+        Account, AccountType, currency,
+        io_util::{prompt_number, prompt_valid},
+    };
+    use chrono::{Datelike, NaiveDate, TimeDelta};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    /// The furthest that a goal's target date can be from when it is set, in days.
+    pub const MAX_TARGET_DAYS: i64 = 36500;
+
+    /// An amount that the owner of an account is saving toward by a date.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct SavingsGoal {
+        /// The number that identifies the goal among the account's goals.
+        pub id: usize,
+        /// What the owner is saving for.
+        pub name: String,
+        /// The amount to save.
+        pub target_amount: f64,
+        /// The currency of the target amount.
+        pub currency: String,
+        /// The date to reach the target amount by.
+        pub target_date: NaiveDate,
+        /// The date that the goal was set.
+        pub start_date: NaiveDate,
+        /// The account's balance, in the goal's currency, when the goal was set.
+        pub start_amount: f64,
+    }
+    impl SavingsGoal {
+        /// Gets the amount that should have been saved by a date for the goal to stay on track, assuming that the
+        /// balance grows evenly from when the goal was set.
+        pub fn expected_amount(&self, date: NaiveDate) -> f64 {
+            let total_days = (self.target_date - self.start_date).num_days();
+
+            if total_days <= 0 || date >= self.target_date {
+                return self.target_amount;
+            }
+
+            let elapsed_days = (date - self.start_date).num_days().max(0);
+
+            self.start_amount + (self.target_amount - self.start_amount) * (elapsed_days as f64 / total_days as f64)
+        }
+    }
+
+    /// How a goal is progressing.
+    pub enum GoalStatus {
+        /// The target amount was saved.
+        Reached,
+        /// The target date passed without saving the target amount.
+        Missed,
+        /// The balance is keeping up with the goal.
+        OnTrack,
+        /// The balance is falling behind the goal.
+        OffTrack,
+    }
+
+    /// Gets the status of a goal given the current amount saved toward it.
+    pub fn status(goal: &SavingsGoal, amount: f64, today: NaiveDate) -> GoalStatus {
+        if amount >= goal.target_amount {
+            GoalStatus::Reached
+        } else if today >= goal.target_date {
+            GoalStatus::Missed
+        } else if amount >= goal.expected_amount(today) {
+            GoalStatus::OnTrack
+        } else {
+            GoalStatus::OffTrack
+        }
+    }
+
+    /// Projects a balance up to a date, returning the balance including its uncredited interest.
+    ///
+    /// Interest follows the bank's daily processing, so it is accrued daily and credited at the start of each month.
+    /// Daily deposits are made every day, and monthly deposits at the start of each month. The projection stops after
+    /// [`MAX_TARGET_DAYS`] days.
+    pub fn project(
+        mut balance: f64,
+        mut accrued_interest: f64,
+        annual_interest_rate: f64,
+        today: NaiveDate,
+        target_date: NaiveDate,
+        daily_deposit: f64,
+        monthly_deposit: f64,
+    ) -> f64 {
+        let mut date = today;
+        let target_date = target_date.min(today + TimeDelta::days(MAX_TARGET_DAYS));
+
+        while date < target_date {
+            date += TimeDelta::days(1);
+
+            if balance > 0.0 {
+                accrued_interest += balance * (annual_interest_rate / 365.0);
+            }
+
+            if date.day() == 1 {
+                balance += (accrued_interest * 100.0).round() / 100.0;
+                accrued_interest = 0.0;
+                balance += monthly_deposit;
+            }
+
+            balance += daily_deposit;
+        }
+
+        balance + accrued_interest
+    }
+
+    /// Gets the deposit needed every period to reach a goal, which is `None` if no deposits fall before the target date.
+    ///
+    /// `project_with` projects the balance by the target date given the deposit made every period.
+    fn required_deposit(target_amount: f64, project_with: impl Fn(f64) -> f64) -> Option<f64> {
+        let without_deposits = project_with(0.0);
+
+        if without_deposits >= target_amount {
+            return Some(0.0);
+        }
+
+        let per_unit = project_with(1.0) - without_deposits;
+
+        if per_unit <= 0.0 {
+            return None;
+        }
+
+        Some(((target_amount - without_deposits) / per_unit * 100.0).ceil() / 100.0)
+    }
+
+    /// Gets an account's balance and uncredited interest in another currency.
+    fn balance_in(account: &Account, code: &str, rates: &HashMap<&str, f64>) -> (f64, f64) {
+        if account.currency == code {
+            return (account.balance, account.accrued_interest);
+        }
+
+        (
+            currency::convert(account.balance, &account.currency.as_str(), &code, rates),
+            currency::convert(account.accrued_interest, &account.currency.as_str(), &code, rates),
+        )
+    }
+
+    /// Sets a new savings goal on an account.
+    ///
+    /// The user is prompted to input what the goal is for, its currency, target amount and target date.
+    pub fn set(account: &mut Account, rates: &HashMap<&str, f64>, today: NaiveDate) {
+        if let AccountType::Loan(_) = account.kind {
+            println!("Loans cannot have savings goals!");
+
+            return;
+        }
+
+        let Some(name) = prompt_valid("Goal Name: ", |input| match input {
+            "" => Err(String::from("Goal name must not be empty!")),
+            name => Ok(name.to_string()),
+        }) else {
+            return;
+        };
+        let Some(code) = currency::prompt_currency("Currency: ") else {
+            return;
+        };
+        let Some(target_amount) = prompt_number(
+            "Target Amount: ",
+            f64::MIN_POSITIVE..=f64::MAX,
+            "Amount must be a positive floating point number!",
+        ) else {
+            return;
+        };
+        let Some(target_date) = prompt_valid("Target Date (YYYY-MM-DD): ", |input| match input.parse::<NaiveDate>() {
+            Ok(date) if date > today && date <= today + TimeDelta::days(MAX_TARGET_DAYS) => Ok(date),
+            Ok(date) if date > today => Err(format!(
+                "Target date must be at most {MAX_TARGET_DAYS} days from today!"
+            )),
+            Ok(_) => Err(String::from("Target date must be after today!")),
+            Err(_) => Err(String::from("Date must be in the YYYY-MM-DD format!")),
+        }) else {
+            return;
+        };
+
+        let goal = SavingsGoal {
+            id: account.goals.iter().map(|g| g.id).max().unwrap_or(0) + 1,
+            name,
+            target_amount,
+            currency: code.to_string(),
+            target_date,
+            start_date: today,
+            start_amount: balance_in(account, code, rates).0,
+        };
+
+        println!();
+
+        print_goal(account, &goal, rates, today);
+
+        account.goals.push(goal);
+    }
+
+    /// Removes one of an account's savings goals.
+    ///
+    /// The user is prompted to choose the goal from the account's goals.
+    pub fn remove(account: &mut Account) {
+        if account.goals.is_empty() {
+            println!("No savings goals are set on this account!");
+
+            return;
+        }
+
+        println!();
+
+        println!("Savings Goals:");
+
+        for goal in &account.goals {
+            println!(
+                "#{} {}: {} {} by {}",
+                goal.id, goal.name, goal.target_amount, goal.currency, goal.target_date
+            );
+        }
+
+        println!();
+
+        let Some(idx) = prompt_valid("Savings Goal ID: ", |input| {
+            match input.trim_start_matches('#').parse::<usize>() {
+                Ok(id) => account
+                    .goals
+                    .iter()
+                    .position(|g| g.id == id)
+                    .ok_or_else(|| String::from("No savings goal with this ID exists!")),
+                Err(_) => Err(String::from("ID must be a positive whole number (integer)!")),
+            }
+        }) else {
+            return;
+        };
+
+        let goal = account.goals.remove(idx);
+
+        println!("Removed: #{} {}", goal.id, goal.name);
+    }
+
+    /// Prints the progress of a goal, and the deposits needed to reach it given the account's interest if the account
+    /// accepts deposits.
+    fn print_goal(account: &Account, goal: &SavingsGoal, rates: &HashMap<&str, f64>, today: NaiveDate) {
+        let (balance, accrued_interest) = balance_in(account, &goal.currency, rates);
+        let progress = (balance / goal.target_amount * 100.0).clamp(0.0, 100.0);
+        let status = status(goal, balance, today);
+        let status_label = match status {
+            GoalStatus::Reached => "REACHED",
+            GoalStatus::Missed => "MISSED",
+            GoalStatus::OnTrack => "ON TRACK",
+            GoalStatus::OffTrack => "OFF TRACK",
+        };
+
+        println!(
+            "#{} {}: {:.2} / {} {} by {} ({progress:.2}%) [{status_label}]",
+            goal.id, goal.name, balance, goal.target_amount, goal.currency, goal.target_date
+        );
+
+        if matches!(status, GoalStatus::Reached | GoalStatus::Missed) {
+            return;
+        }
+
+        let annual_interest_rate = account.kind.annual_interest_rate();
+        let project_with = |daily_deposit: f64, monthly_deposit: f64| {
+            project(
+                balance,
+                accrued_interest,
+                annual_interest_rate,
+                today,
+                goal.target_date,
+                daily_deposit,
+                monthly_deposit,
+            )
+        };
+
+        println!("    Expected by Today: {:.2}", goal.expected_amount(today));
+        println!("    Projected Without Deposits: {:.2}", project_with(0.0, 0.0));
+
+        if let Err(reason) = account.check_deposit() {
+            println!("    Required Deposits: N/A ({reason})");
+
+            return;
+        }
+
+        match required_deposit(goal.target_amount, |d| project_with(d, 0.0)) {
+            Some(amount) => println!("    Required Daily Deposit: {amount:.2}"),
+            None => println!("    Required Daily Deposit: N/A"),
+        }
+
+        match required_deposit(goal.target_amount, |d| project_with(0.0, d)) {
+            Some(amount) => println!("    Required Monthly Deposit: {amount:.2}"),
+            None => println!("    Required Monthly Deposit: N/A (No month starts before the target date)"),
+        }
+    }
+
+    /// Prints the progress of all of an account's savings goals, flagging those that are off track.
+    pub fn print_goals(account: &Account, rates: &HashMap<&str, f64>, today: NaiveDate) {
+        if account.goals.is_empty() {
+            println!("No savings goals are set on this account!");
+
+            return;
+        }
+
+        println!("Account: {} ({})", account.number, account.name);
+        println!("Interest Rate: {}%", account.kind.annual_interest_rate() * 100.0);
+
+        for goal in &account.goals {
+            println!();
+
+            print_goal(account, goal, rates, today);
+        }
+
+        let off_track_cnt = account
+            .goals
+            .iter()
+            .filter(|g| {
+                matches!(
+                    status(g, balance_in(account, &g.currency, rates).0, today),
+                    GoalStatus::OffTrack | GoalStatus::Missed
+                )
+            })
+            .count();
+
+        if off_track_cnt > 0 {
+            println!();
+
+            println!("{off_track_cnt} savings goal(s) are off track!");
+        }
+    }
+}
+
+mod scenario {
+    use crate::{
+        Account, AccountType, config,
+        io_util::{confirm, print_ordered_list, prompt_file, prompt_number, prompt_option, prompt_valid},
+        prompt_account_idx,
+    };
+    use serde::Serialize;
+    use std::{error, fmt, fmt::Write as _, fs, path::Path};
+
+    /// The titles of the schemes that interest can be compounded with.
+    pub const COMPOUNDING_TITLES: [&str; 4] = ["Daily", "Monthly", "Annually", "Simple"];
+    /// The longest period that scenarios can be projected over, in months.
+    pub const MAX_PROJECTION_MONTHS: u32 = 1200;
+
+    /// How often earned interest is added to the balance that earns interest.
+    #[derive(Clone, Copy)]
+    pub enum Compounding {
+        /// Interest is compounded every day.
+        Daily,
+        /// Interest is compounded at the end of every month.
+        Monthly,
+        /// Interest is compounded at the end of every twelve months.
+        Annually,
+        /// Interest is never compounded, so it is only earned on the deposits.
+        Simple,
+    }
+    impl fmt::Display for Compounding {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.pad(match self {
+                Compounding::Daily => COMPOUNDING_TITLES[0],
+                Compounding::Monthly => COMPOUNDING_TITLES[1],
+                Compounding::Annually => COMPOUNDING_TITLES[2],
+                Compounding::Simple => COMPOUNDING_TITLES[3],
+            })
+        }
+    }
+
+    /// A set of assumptions to project a balance under.
+    pub struct Scenario {
+        /// The annual interest rate percentage.
+        pub annual_interest_rate: f64,
+        /// How often interest is compounded.
+        pub compounding: Compounding,
+        /// The amount deposited at the end of every month.
+        pub monthly_contribution: f64,
+    }
+
+    /// The projected outcome of a scenario, as printed and exported.
+    #[derive(Serialize)]
+    pub struct ScenarioResult {
+        /// The scenario's number in the comparison.
+        pub scenario: usize,
+        /// The annual interest rate percentage, multiplied by 100.
+        pub annual_interest_rate: f64,
+        /// How often interest is compounded.
+        pub compounding: String,
+        /// The amount deposited at the end of every month.
+        pub monthly_contribution: f64,
+        /// The balance at the end of the projection.
+        pub final_balance: f64,
+        /// The total interest earned over the projection.
+        pub total_interest: f64,
+    }
+
+    /// Projects a balance over a number of months under a scenario, returning the final balance and total interest.
+    pub fn project(balance: f64, month_cnt: u32, scenario: &Scenario) -> (f64, f64) {
+        let monthly_rate = scenario.annual_interest_rate / 12.0;
+        let mut principal = balance;
+        let mut compounded_interest = 0.0;
+        let mut pending_interest = 0.0;
+
+        for month in 1..=month_cnt {
+            let earning_balance = principal + compounded_interest;
+
+            match scenario.compounding {
+                Compounding::Daily => {
+                    compounded_interest +=
+                        earning_balance * ((1.0 + scenario.annual_interest_rate / 365.0).powf(365.0 / 12.0) - 1.0);
+                }
+                Compounding::Monthly => compounded_interest += earning_balance * monthly_rate,
+                Compounding::Annually => {
+                    pending_interest += earning_balance * monthly_rate;
+
+                    if month % 12 == 0 {
+                        compounded_interest += pending_interest;
+                        pending_interest = 0.0;
+                    }
+                }
+                Compounding::Simple => pending_interest += principal * monthly_rate,
+            }
+
+            principal += scenario.monthly_contribution;
+        }
+
+        let total_interest = ((compounded_interest + pending_interest) * 100.0).round() / 100.0;
+
+        (principal + total_interest, total_interest)
+    }
+
+    /// Prompts a CLI user to input a scenario.
+    fn prompt_scenario(default_rate: f64) -> Option<Scenario> {
+        let msg = format!("Annual Interest Rate (%, Leave Blank for {}): ", default_rate * 100.0);
+        let annual_interest_rate = prompt_valid(&msg, |input| match input {
+            "" => Ok(default_rate),
+            input => match input.parse::<f64>() {
+                Ok(rate) if (0.0..=f64::MAX).contains(&rate) => Ok(rate / 100.0),
+                _ => Err(String::from(
+                    "Interest rate must be a non-negative floating point number!",
+                )),
+            },
+        })?;
+
+        println!();
+
+        println!("Compounding Options:");
+        print_ordered_list(&COMPOUNDING_TITLES);
+
+        println!();
+
+        let compounding = match prompt_option(
+            "Compounding: ",
+            COMPOUNDING_TITLES.len(),
+            "No compounding scheme with this ID exists!",
+        )? {
+            0 => Compounding::Daily,
+            1 => Compounding::Monthly,
+            2 => Compounding::Annually,
+            _ => Compounding::Simple,
+        };
+        let monthly_contribution = prompt_valid("Monthly Contribution: ", |input| match input {
+            "" => Ok(0.0),
+            input => match input.parse::<f64>() {
+                Ok(amount) if (0.0..=f64::MAX).contains(&amount) => Ok(amount),
+                _ => Err(String::from(
+                    "Contribution must be a non-negative floating point number!",
+                )),
+            },
+        })?;
+
+        Some(Scenario {
+            annual_interest_rate,
+            compounding,
+            monthly_contribution,
+        })
+    }
+
+    /// Renders the results of a comparison as an aligned plain text table.
+    fn render_table(results: &[ScenarioResult]) -> String {
+        let mut table = String::new();
+
+        let _ = writeln!(
+            table,
+            "{:<3} | {:<8} | {:<11} | {:<12} | {:<14} | {:<14} |",
+            "#", "Rate (%)", "Compounding", "Contribution", "Final Balance", "Total Interest"
+        );
+
+        for result in results {
+            let _ = writeln!(
+                table,
+                "{:<3} | {:<8} | {:<11} | {:<12.2} | {:<14.2} | {:<14.2} |",
+                result.scenario,
+                result.annual_interest_rate,
+                result.compounding,
+                result.monthly_contribution,
+                result.final_balance,
+                result.total_interest
+            );
+        }
+
+        table
+    }
+
+    /// Writes the results of a comparison to a file, rendered based on the file's extension.
+    pub fn write_comparison(path: &str, results: &[ScenarioResult]) -> Result<(), Box<dyn error::Error>> {
+        match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("csv") => {
+                let mut fw = csv::Writer::from_path(path)?;
+
+                for result in results {
+                    fw.serialize(result)?;
+                }
+
+                fw.flush()?;
+            }
+            Some("txt") => fs::write(path, render_table(results))?,
+            _ => return Err("Comparison must be a .csv or .txt file!".into()),
+        }
+
+        Ok(())
+    }
+
+    /// Compares the projections of an account's balance, or a hypothetical balance, under several scenarios.
+    ///
+    /// The user is prompted to choose the balance, the number of months to project over, and each scenario, before the
+    /// comparison can be exported.
+    pub fn compare_scenarios(accounts: &[Account]) {
+        let Some(is_existing_account) = confirm("Project an existing account? (Y/N): ") else {
+            return;
+        };
+        let (balance, default_rate) = if is_existing_account {
+            let Some(idx) = prompt_account_idx(accounts) else {
+                return;
+            };
+
+            if let AccountType::Loan(_) = accounts[idx].kind {
+                println!("Loans follow their amortization schedule, which is shown by Show Interest Amount!");
+
+                return;
+            }
+
+            (accounts[idx].balance, accounts[idx].kind.annual_interest_rate())
+        } else {
+            let Some(balance) = prompt_number(
+                "Starting Balance: ",
+                f64::MIN..=f64::MAX,
+                "Balance must be a floating point number!",
+            ) else {
+                return;
+            };
+
+            (balance, config::get().interest.savings_annual_rate)
+        };
+
+        let Some(month_cnt) = prompt_number(
+            "Number of Months: ",
+            1..=MAX_PROJECTION_MONTHS,
+            &format!("Number must be a whole number (integer) from 1 to {MAX_PROJECTION_MONTHS}!"),
+        ) else {
+            return;
+        };
+        let mut results = Vec::<ScenarioResult>::new();
+
+        loop {
+            println!();
+
+            println!("Scenario {}", results.len() + 1);
+
+            if let Some(scenario) = prompt_scenario(default_rate) {
+                let (final_balance, total_interest) = project(balance, month_cnt, &scenario);
+
+                results.push(ScenarioResult {
+                    scenario: results.len() + 1,
+                    annual_interest_rate: scenario.annual_interest_rate * 100.0,
+                    compounding: scenario.compounding.to_string(),
+                    monthly_contribution: scenario.monthly_contribution,
+                    final_balance,
+                    total_interest,
+                });
+            }
+
+            println!();
+
+            if confirm("Add another scenario? (Y/N): ") != Some(true) {
+                break;
+            }
+        }
+
+        if results.is_empty() {
+            return;
+        }
+
+        println!();
+
+        println!("Starting Balance: {balance}");
+        println!("Number of Months: {month_cnt}");
+
+        println!();
+
+        print!("{}", render_table(&results));
+
+        println!();
+
+        if confirm("Export the comparison? (Y/N): ") == Some(true)
+            && let Some(path) = prompt_file("Comparison File (.csv/.txt): ", &["csv", "txt"])
+        {
+            match write_comparison(&path, &results) {
+                Ok(()) => println!("Exported {} scenarios to {path}", results.len()),
+                Err(err) => println!("Failed to write the comparison: {err}"),
+            }
+        }
+    }
+}
+
+mod batch {
+    use crate::{
+        Account, TransactionKind, account_number, clock, currency, fx_position,
+        io_util::{confirm, prompt_file},
+        storage,
+        withdrawal_limit::WithdrawalLimitTable,
+    };
+    use serde::{Deserialize, Serialize};
+    use std::{collections::HashMap, error};
+
+    /// A transaction to apply, as read from a row of a batch file.
+    #[derive(Deserialize)]
+    pub struct BatchRow {
+        /// The number or name of the account.
+        pub account: String,
+        /// The kind of transaction, either "deposit" or "withdrawal".
+        #[serde(rename = "type")]
+        pub kind: String,
+        /// The currency that the amount is given in.
+        pub currency: String,
+        /// The amount to deposit or withdraw.
+        pub amount: f64,
+    }
+
+    /// The outcome of a row of a batch file, as written to the results file.
+    #[derive(Serialize)]
+    pub struct BatchResult {
+        /// The row's line number in the batch file.
+        pub row: usize,
+        /// The number of the account, or what was given if it was not found.
+        pub account: String,
+        /// Whether the transaction was applied.
+        pub status: &'static str,
+        /// The transaction's reference number if it was applied, or why it failed otherwise.
+        pub detail: String,
+        /// The balance of the account after the row, if the account was found.
+        pub balance: Option<f64>,
+    }
+
+    /// Finds the open account with a number, or with a name that no other open account shares.
+    pub fn find_account_idx(accounts: &[Account], query: &str) -> Result<usize, String> {
+        if !query.is_empty() && query.chars().all(|c| c.is_ascii_digit()) {
+            if !account_number::is_valid(query) {
+                return Err(String::from("Account number is invalid!"));
+            }
+
+            return accounts
+                .iter()
+                .position(|a| !a.is_closed && a.number == query)
+                .ok_or(String::from("No account with this number exists!"));
+        }
+
+        let mut matching_idxs = accounts
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| !a.is_closed && a.name == query)
+            .map(|(i, _)| i);
+
+        match (matching_idxs.next(), matching_idxs.next()) {
+            (Some(idx), None) => Ok(idx),
+            (Some(_), Some(_)) => Err(String::from("Several accounts have this name, use its number instead!")),
+            (None, _) => Err(String::from("No account with this name exists!")),
+        }
+    }
+
+    /// Applies a row of a batch file to an account, returning the transaction's reference number.
+    ///
+    /// Withdrawals from a time deposit before it matures are rejected instead of being charged the early withdrawal
+    /// penalty.
+    fn apply_row(
+        account: &mut Account,
+        row: &BatchRow,
+        rates: &HashMap<&str, f64>,
+        limit_table: &WithdrawalLimitTable,
+    ) -> Result<String, String> {
+        let code = row.currency.trim().to_uppercase();
+
+        let Some(&code) = currency::CURRENCIES_CODES.iter().find(|&&c| c == code) else {
+            return Err(format!("No currency with the code \"{code}\" exists!"));
+        };
+
+        if !currency::is_enabled(code) {
+            return Err(format!("Currency {code} is not enabled!"));
+        }
+
+        if !row.amount.is_finite() || row.amount <= 0.0 {
+            return Err(String::from("Amount must be a positive number!"));
+        }
+
+        let amount = if code == "PHP" {
+            row.amount
+        } else {
+            currency::convert(row.amount, &code, &"PHP", rates)
+        };
+        let today = clock::today();
+
+        match row.kind.trim().to_lowercase().as_str() {
+            "deposit" => {
+                account.check_deposit()?;
+
+                let (reference, ..) = account.deposit_in(code, row.amount, rates, false, today);
+
+                Ok(reference)
+            }
+            "withdrawal" => {
+                let limits = account.withdrawal_limits(limit_table);
+
+                account.check_withdrawal_allowed(today, &limits)?;
+                account.check_withdrawal_limits(amount, today, &limits)?;
+
+                let charges = account.withdrawal_charges(amount, today)?;
+
+                charges.check_no_penalty()?;
+
+                fx_position::book(code, -row.amount, amount);
+
+                Ok(account.post_withdrawal(TransactionKind::Withdrawal, amount, &charges))
+            }
+            kind => Err(format!(
+                "No transaction type \"{kind}\" exists, use deposit or withdrawal!"
+            )),
+        }
+    }
+
+    /// Applies every row of a batch file to the accounts, in order.
+    ///
+    /// In all-or-nothing mode, the accounts are only changed if every row succeeds. Otherwise, only the failed rows are
+    /// skipped.
+    pub fn process_batch(
+        accounts: &mut Vec<Account>,
+        path: &str,
+        rates: &HashMap<&str, f64>,
+        limit_table: &WithdrawalLimitTable,
+        is_all_or_nothing: bool,
+    ) -> Result<Vec<BatchResult>, Box<dyn error::Error>> {
+        let mut fr = csv::ReaderBuilder::new().trim(csv::Trim::All).from_path(path)?;
+        let mut staged_accounts = accounts.clone();
+        let fx_positions = fx_position::snapshot();
+        let mut results = Vec::<BatchResult>::new();
+
+        storage::hold_journal();
+
+        for (i, row) in fr.deserialize::<BatchRow>().enumerate() {
+            // The header is the first line, so the rows start on the second.
+            let line = i + 2;
+
+            let row = match row {
+                Ok(row) => row,
+                Err(err) => {
+                    results.push(BatchResult {
+                        row: line,
+                        account: String::new(),
+                        status: "FAILED",
+                        detail: format!("Row could not be read: {err}"),
+                        balance: None,
+                    });
+
+                    continue;
+                }
+            };
+
+            let result = match find_account_idx(&staged_accounts, row.account.trim()) {
+                Ok(idx) => {
+                    let account = &mut staged_accounts[idx];
+                    let outcome = apply_row(account, &row, rates, limit_table);
+
+                    BatchResult {
+                        row: line,
+                        account: account.number.clone(),
+                        status: if outcome.is_ok() { "OK" } else { "FAILED" },
+                        detail: outcome.unwrap_or_else(|reason| reason),
+                        balance: Some(account.balance),
+                    }
+                }
+                Err(reason) => BatchResult {
+                    row: line,
+                    account: row.account,
+                    status: "FAILED",
+                    detail: reason,
+                    balance: None,
+                },
+            };
+
+            results.push(result);
+        }
+
+        let is_rolled_back = is_all_or_nothing && results.iter().any(|r| r.status == "FAILED");
+
+        if is_rolled_back {
+            for result in results.iter_mut() {
+                if result.status == "OK" {
+                    result.status = "ROLLED BACK";
+                }
+
+                result.balance = None;
+            }
+
+            fx_position::restore(fx_positions);
+        } else {
+            *accounts = staged_accounts;
+        }
+
+        if let Err(err) = storage::release_journal(!is_rolled_back) {
+            println!("Warning: Failed to journal the batch's transactions: {err}");
+        }
+
+        Ok(results)
+    }
+
+    /// Writes the outcomes of a batch file's rows to a CSV file.
+    pub fn write_results(path: &str, results: &[BatchResult]) -> Result<(), Box<dyn error::Error>> {
+        let mut fw = csv::Writer::from_path(path)?;
+
+        for result in results {
+            fw.serialize(result)?;
+        }
+
+        fw.flush()?;
+
+        Ok(())
+    }
+
+    /// Processes a batch file of transactions and writes the outcome of each row to a results file.
+    ///
+    /// The user is prompted to input both files and whether the batch is all-or-nothing. Batch files are CSV files
+    /// with the `account`, `type`, `currency` and `amount` columns.
+    pub fn import_batch(accounts: &mut Vec<Account>, rates: &HashMap<&str, f64>, limit_table: &WithdrawalLimitTable) {
+        let Some(path) = prompt_file("Batch File (.csv): ", &["csv"]) else {
+            return;
+        };
+        let Some(results_path) = prompt_file("Results File (.csv): ", &["csv"]) else {
+            return;
+        };
+        let Some(is_all_or_nothing) = confirm("Apply only if every row succeeds? (Y/N): ") else {
+            return;
+        };
+
+        println!();
+
+        let results = match process_batch(accounts, &path, rates, limit_table, is_all_or_nothing) {
+            Ok(results) => results,
+            Err(err) => {
+                println!("Failed to read the batch file: {err}");
+
+                return;
+            }
+        };
+        let applied_cnt = results.iter().filter(|r| r.status == "OK").count();
+
+        println!("Applied {applied_cnt} of {} rows", results.len());
+
+        if applied_cnt < results.len() && is_all_or_nothing {
+            println!("The batch was rolled back as some rows failed!");
+        }
+
+        for result in results.iter().filter(|r| r.status == "FAILED") {
+            println!("Row {}: {}", result.row, result.detail);
+        }
+
+        if let Err(err) = write_results(&results_path, &results) {
+            println!("Failed to write the results file: {err}");
+        } else {
+            println!("Wrote the results to {results_path}");
+        }
+    }
+}
+
+mod receipt {
+    use chacha20poly1305::aead::{OsRng, rand_core::RngCore};
+    use chrono::{DateTime, Local};
+    use std::{
+        error,
+        fmt::Write as _,
+        fs,
+        path::PathBuf,
+        sync::{
+            OnceLock,
+            atomic::{AtomicU64, Ordering},
+        },
+    };
+
+    /// The directory that receipts are saved to, if they are saved.
+    static DIRECTORY: OnceLock<PathBuf> = OnceLock::new();
+    /// The sequence number of the next reference number.
+    static NEXT_SEQUENCE: AtomicU64 = AtomicU64::new(1);
+    /// The random number that tells apart the reference numbers of sessions running at the same time.
+    static SESSION_TAG: OnceLock<u32> = OnceLock::new();
+
+    /// Saves every receipt issued from now on to a directory.
+    pub fn save_to(dir: PathBuf) {
+        DIRECTORY.get_or_init(|| dir);
+    }
+
+    /// Generates a unique reference number for a transaction.
+    ///
+    /// The number starts with the system's time when it was generated, rather than the bank's, followed by a random tag
+    /// drawn once per session, so that numbers from different sessions do not collide even within the same second. The
+    /// session's sequence number comes last and is never reused, widening past four digits if needed.
+    pub fn next_reference() -> String {
+        format!(
+            "{}{:06}{:04}",
+            Local::now().format("%y%m%d%H%M%S"),
+            SESSION_TAG.get_or_init(|| OsRng.next_u32() % 1_000_000),
+            NEXT_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+
+    /// A proof of a transaction made by a user.
+    pub struct Receipt {
+        /// The reference number of the transaction.
+        pub reference: String,
+        /// When the transaction was made.
+        pub timestamp: DateTime<Local>,
+        /// The number of the account.
+        pub account_number: String,
+        /// The name of the owner of the account.
+        pub account_name: String,
+        /// The kind of transaction made.
+        pub kind: String,
+        /// The currency that the amount was given in.
+        pub currency: String,
+        /// The amount given, in its own currency.
+        pub amount: f64,
+        /// The exchange rate applied to convert the amount to the account's currency.
+        pub rate: f64,
+        /// The amount in the account's currency.
+        pub converted_amount: f64,
+        /// The fees and penalties charged on top of the amount.
+        pub charges: f64,
+        /// The balance of the account after the transaction.
+        pub balance: f64,
+    }
+    impl Receipt {
+        /// Renders the receipt as plain text.
+        pub fn render(&self) -> String {
+            let mut text = String::new();
+
+            let _ = writeln!(text, "Reference Number: {}", self.reference);
+            let _ = writeln!(text, "Date: {}", self.timestamp.format("%Y-%m-%d %H:%M:%S"));
+            let _ = writeln!(text, "Account: {} ({})", self.account_number, self.account_name);
+            let _ = writeln!(text, "Transaction: {}", self.kind);
+            let _ = writeln!(text, "Amount: {} {}", self.amount, self.currency);
+
+            if self.currency != "PHP" {
+                let _ = writeln!(text, "Exchange Rate: {}", self.rate);
+                let _ = writeln!(text, "Converted Amount: {} PHP", self.converted_amount);
+            }
+
+            if self.charges > 0.0 {
+                let _ = writeln!(text, "Charges: {} PHP", self.charges);
+            }
+
+            let _ = writeln!(text, "Resulting Balance: {} PHP", self.balance);
+
+            text
+        }
+
+        /// Saves the receipt to a directory, naming the file after its reference number.
+        fn save(&self, dir: &PathBuf) -> Result<PathBuf, Box<dyn error::Error>> {
+            fs::create_dir_all(dir)?;
+
+            let path = dir.join(format!("{}.txt", self.reference));
+
+            fs::write(&path, self.render())?;
+
+            Ok(path)
+        }
+
+        /// Prints the receipt, also saving it if receipts are saved.
+        pub fn issue(&self) {
+            println!();
+
+            println!("Receipt:");
+            print!("{}", self.render());
+
+            if let Some(dir) = DIRECTORY.get() {
+                match self.save(dir) {
+                    Ok(path) => println!("Saved the receipt to {}", path.display()),
+                    Err(err) => println!("Failed to save the receipt: {err}"),
+                }
+            }
+        }
+    }
+}
+
+mod statement {
+    use crate::{
+        Account, TransactionKind, clock,
+        io_util::{prompt_file, prompt_valid},
+    };
+    use chrono::{Datelike, NaiveDate};
+    use serde::Serialize;
+    use std::{error, fmt::Write as _, fs, path::Path};
+
+    /// A transaction as listed on a statement.
+    #[derive(Serialize)]
+    pub struct StatementLine {
+        /// The date of the transaction.
+        pub date: NaiveDate,
+        /// The reference number of the transaction.
+        pub reference: String,
+        /// What the transaction was.
+        pub description: String,
+        /// The amount that the transaction took from the balance.
+        pub debit: Option<f64>,
+        /// The amount that the transaction added to the balance.
+        pub credit: Option<f64>,
+        /// The balance after the transaction.
+        pub balance: f64,
+    }
+
+    /// A summary of an account's activity over a period.
+    pub struct Statement {
+        /// The number of the account.
+        pub account_number: String,
+        /// The name of the owner of the account.
+        pub account_name: String,
+        /// The product that the account is registered as.
+        pub account_type: String,
+        /// The currency that the account's balance is based on.
+        pub currency: String,
+        /// The first date of the period.
+        pub start_date: NaiveDate,
+        /// The last date of the period.
+        pub end_date: NaiveDate,
+        /// The balance before the period.
+        pub opening_balance: f64,
+        /// Every transaction in the period, from oldest to newest.
+        pub lines: Vec<StatementLine>,
+        /// The total interest credited to the account in the period.
+        pub total_interest_earned: f64,
+        /// The total interest charged on an overdrawn balance or a loan's principal in the period.
+        pub total_interest_charged: f64,
+        /// The total fees and penalties charged in the period.
+        pub total_fees: f64,
+        /// The balance at the end of the period.
+        pub closing_balance: f64,
+    }
+
+    /// Generates the statement of an account for a period, which includes both its start and end dates.
+    pub fn generate(account: &Account, start_date: NaiveDate, end_date: NaiveDate) -> Statement {
+        let mut opening_balance = 0.0;
+        let mut lines = Vec::<StatementLine>::new();
+        let mut total_interest_earned = 0.0;
+        let mut total_interest_charged = 0.0;
+        let mut total_fees = 0.0;
+        let mut prev_balance = 0.0;
+
+        for transaction in &account.transactions {
+            let date = transaction.timestamp.date_naive();
+            let change = transaction.balance - prev_balance;
+
+            prev_balance = transaction.balance;
+
+            if date < start_date {
+                opening_balance = transaction.balance;
+
+                continue;
+            } else if date > end_date {
+                break;
+            }
+
+            match transaction.kind {
+                TransactionKind::Interest => total_interest_earned += transaction.amount,
+                TransactionKind::OverdraftInterest | TransactionKind::LoanInterest => {
+                    total_interest_charged += transaction.amount;
+                }
+                TransactionKind::Penalty | TransactionKind::OverdraftFee | TransactionKind::LatePaymentPenalty => {
+                    total_fees += transaction.amount;
+                }
+                _ => {}
+            }
+
+            lines.push(StatementLine {
+                date,
+                reference: transaction.reference.clone(),
+                description: transaction.kind.to_string(),
+                debit: (change < 0.0).then_some(-change),
+                credit: (change >= 0.0).then_some(change),
+                balance: transaction.balance,
+            });
+        }
+
+        Statement {
+            account_number: account.number.clone(),
+            account_name: account.name.clone(),
+            account_type: account.kind.to_string(),
+            currency: account.currency.clone(),
+            start_date,
+            end_date,
+            opening_balance,
+            closing_balance: lines.last().map_or(opening_balance, |l| l.balance),
+            lines,
+            total_interest_earned,
+            total_interest_charged,
+            total_fees,
+        }
+    }
+
+    /// Formats an optional amount, leaving it blank if there is none.
+    fn fmt_amount(amount: Option<f64>) -> String {
+        amount.map_or(String::new(), |a| format!("{a:.2}"))
+    }
+
+    /// Renders a statement as plain text.
+    pub fn render_text(statement: &Statement) -> String {
+        let mut text = String::new();
+
+        let _ = writeln!(text, "Account Statement");
+        let _ = writeln!(text);
+        let _ = writeln!(text, "Account Number: {}", statement.account_number);
+        let _ = writeln!(text, "Account Name: {}", statement.account_name);
+        let _ = writeln!(text, "Account Type: {}", statement.account_type);
+        let _ = writeln!(text, "Currency: {}", statement.currency);
+        let _ = writeln!(text, "Period: {} to {}", statement.start_date, statement.end_date);
+        let _ = writeln!(text);
+        let _ = writeln!(text, "Opening Balance: {:.2}", statement.opening_balance);
+        let _ = writeln!(text);
+        let _ = writeln!(
+            text,
+            "{:<10} | {:<22} | {:<20} | {:<12} | {:<12} | {:<12} |",
+            "Date", "Reference", "Description", "Debit", "Credit", "Balance"
+        );
+
+        for line in &statement.lines {
+            let _ = writeln!(
+                text,
+                "{:<10} | {:<22} | {:<20} | {:<12} | {:<12} | {:<12.2} |",
+                line.date,
+                line.reference,
+                line.description,
+                fmt_amount(line.debit),
+                fmt_amount(line.credit),
+                line.balance
+            );
+        }
+
+        let _ = writeln!(text);
+        let _ = writeln!(text, "Total Interest Earned: {:.2}", statement.total_interest_earned);
+        let _ = writeln!(text, "Total Interest Charged: {:.2}", statement.total_interest_charged);
+        let _ = writeln!(text, "Total Fees: {:.2}", statement.total_fees);
+        let _ = writeln!(text, "Closing Balance: {:.2}", statement.closing_balance);
+
+        text
+    }
+
+    /// Renders a statement as CSV, with the opening and closing balances as the first and last rows.
+    pub fn render_csv(statement: &Statement) -> Result<String, Box<dyn error::Error>> {
+        let mut fw = csv::Writer::from_writer(Vec::<u8>::new());
+
+        fw.serialize(StatementLine {
+            date: statement.start_date,
+            reference: String::new(),
+            description: String::from("Opening Balance"),
+            debit: None,
+            credit: None,
+            balance: statement.opening_balance,
+        })?;
+
+        for line in &statement.lines {
+            fw.serialize(line)?;
+        }
+
+        fw.serialize(StatementLine {
+            date: statement.end_date,
+            reference: String::new(),
+            description: String::from("Closing Balance"),
+            debit: None,
+            credit: None,
+            balance: statement.closing_balance,
+        })?;
+
+        Ok(String::from_utf8(fw.into_inner()?)?)
+    }
+
+    /// Escapes the characters of a text that HTML treats as markup.
+    fn escape_html(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    /// Renders a statement as a self-contained HTML page.
+    pub fn render_html(statement: &Statement) -> String {
+        let mut html = String::new();
+
+        let _ = writeln!(html, "<!DOCTYPE html>");
+        let _ = writeln!(html, "<html lang=\"en\">");
+        let _ = writeln!(html, "<head>");
+        let _ = writeln!(html, "<meta charset=\"utf-8\">");
+        let _ = writeln!(
+            html,
+            "<title>Statement {} ({} to {})</title>",
+            escape_html(&statement.account_number),
+            statement.start_date,
+            statement.end_date
+        );
+        let _ = writeln!(
+            html,
+            "<style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse}}\
+             th,td{{border:1px solid #999;padding:4px 8px}}td.amount{{text-align:right}}</style>"
+        );
+        let _ = writeln!(html, "</head>");
+        let _ = writeln!(html, "<body>");
+        let _ = writeln!(html, "<h1>Account Statement</h1>");
+        let _ = writeln!(html, "<p>");
+
+        for (label, value) in [
+            ("Account Number", statement.account_number.as_str()),
+            ("Account Name", statement.account_name.as_str()),
+            ("Account Type", statement.account_type.as_str()),
+            ("Currency", statement.currency.as_str()),
+        ] {
+            let _ = writeln!(html, "<b>{label}:</b> {}<br>", escape_html(value));
+        }
+
+        let _ = writeln!(
+            html,
+            "<b>Period:</b> {} to {}</p>",
+            statement.start_date, statement.end_date
+        );
+        let _ = writeln!(html, "<p><b>Opening Balance:</b> {:.2}</p>", statement.opening_balance);
+        let _ = writeln!(html, "<table>");
+        let _ = writeln!(
+            html,
+            "<tr><th>Date</th><th>Reference</th><th>Description</th><th>Debit</th><th>Credit</th><th>Balance</th></tr>"
+        );
+
+        for line in &statement.lines {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td>\
+                 <td class=\"amount\">{:.2}</td></tr>",
+                line.date,
+                line.reference,
+                escape_html(&line.description),
+                fmt_amount(line.debit),
+                fmt_amount(line.credit),
+                line.balance
+            );
+        }
+
+        let _ = writeln!(html, "</table>");
+        let _ = writeln!(
+            html,
+            "<p><b>Total Interest Earned:</b> {:.2}<br>",
+            statement.total_interest_earned
+        );
+        let _ = writeln!(
+            html,
+            "<b>Total Interest Charged:</b> {:.2}<br>",
+            statement.total_interest_charged
+        );
+        let _ = writeln!(html, "<b>Total Fees:</b> {:.2}<br>", statement.total_fees);
+        let _ = writeln!(html, "<b>Closing Balance:</b> {:.2}</p>", statement.closing_balance);
+        let _ = writeln!(html, "</body>");
+        let _ = writeln!(html, "</html>");
+
+        html
+    }
+
+    /// Writes a statement to a file, rendered based on the file's extension.
+    pub fn write_statement(path: &str, statement: &Statement) -> Result<(), Box<dyn error::Error>> {
+        let contents = match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("txt") => render_text(statement),
+            Some("csv") => render_csv(statement)?,
+            Some("html") => render_html(statement),
+            _ => return Err("Statement must be a .txt, .csv or .html file!".into()),
+        };
+
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    /// Prompts for a date no earlier than a minimum date, returning a default date if nothing is inputted.
+    fn prompt_date(msg: &str, default: NaiveDate, min_date: NaiveDate) -> Option<NaiveDate> {
+        prompt_valid(msg, |input| {
+            let date = match input {
+                "" => default,
+                input => input
+                    .parse::<NaiveDate>()
+                    .map_err(|_| String::from("Date must be in the YYYY-MM-DD format!"))?,
+            };
+
+            if date < min_date {
+                return Err(String::from("Period end must not be before the period start!"));
+            }
+
+            Ok(date)
+        })
+    }
+
+    /// Exports the statement of an account for a period to a file.
+    ///
+    /// The user is prompted to input the period, which defaults to the current month so far, and the file to export
+    /// to.
+    pub fn export_statement(account: &Account) {
+        let today = clock::today();
+
+        let Some(start_date) = prompt_date(
+            "Period Start (YYYY-MM-DD, blank for the start of this month): ",
+            today.with_day(1).unwrap_or(today),
+            NaiveDate::MIN,
+        ) else {
+            return;
+        };
+        let Some(end_date) = prompt_date("Period End (YYYY-MM-DD, blank for today): ", today, start_date) else {
+            return;
+        };
+
+        let Some(path) = prompt_file("Statement File (.txt/.csv/.html): ", &["txt", "csv", "html"]) else {
+            return;
+        };
+        let statement = generate(account, start_date, end_date);
+
+        println!();
+
+        match write_statement(&path, &statement) {
+            Ok(()) => println!(
+                "Exported a statement of {} transactions to {path}",
+                statement.lines.len()
+            ),
+            Err(err) => println!("Failed to write the statement: {err}"),
+        }
+    }
+}
+
+mod withdrawal_limit {
+    use crate::{
+        ACCOUNT_TYPE_TITLES, config,
+        io_util::{print_ordered_list, prompt_option, prompt_valid},
+    };
+    use serde::Deserialize;
+    use std::{collections::HashMap, fmt};
+
+    /// The titles of the periods that the number of withdrawals can be limited over.
+    pub const LIMIT_PERIOD_TITLES: [&str; 2] = ["Daily", "Monthly"];
+
+    /// The periods that the number of withdrawals can be limited over.
+    #[derive(Clone, Copy, PartialEq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum LimitPeriod {
+        /// The calendar day of the withdrawal.
+        Daily,
+        /// The calendar month of the withdrawal.
+        Monthly,
+    }
+    impl fmt::Display for LimitPeriod {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.pad(match self {
+                LimitPeriod::Daily => LIMIT_PERIOD_TITLES[0],
+                LimitPeriod::Monthly => LIMIT_PERIOD_TITLES[1],
+            })
+        }
+    }
+
+    /// The limits on the withdrawals from an account, with amounts in PHP and `None` meaning no limit.
+    #[derive(Clone, Copy, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct WithdrawalLimits {
+        /// The largest amount that a single withdrawal can take.
+        pub max_single_amount: Option<f64>,
+        /// The largest total amount that can be withdrawn in a calendar day.
+        pub max_daily_amount: Option<f64>,
+        /// The largest number of withdrawals that can be made per period.
+        #[serde(rename = "max_count")]
+        pub max_cnt: Option<usize>,
+        /// The period that the number of withdrawals is limited over.
+        #[serde(rename = "count_period")]
+        pub cnt_period: LimitPeriod,
+    }
+    impl Default for WithdrawalLimits {
+        fn default() -> Self {
+            WithdrawalLimits::NONE
+        }
+    }
+    impl WithdrawalLimits {
+        /// Limits that allow any withdrawal.
+        pub const NONE: WithdrawalLimits = WithdrawalLimits {
+            max_single_amount: None,
+            max_daily_amount: None,
+            max_cnt: None,
+            cnt_period: LimitPeriod::Daily,
+        };
+        /// The limits that savings accounts have unless configured otherwise.
+        pub const SAVINGS_DEFAULT: WithdrawalLimits = WithdrawalLimits {
+            max_single_amount: Some(50_000.0),
+            max_daily_amount: Some(100_000.0),
+            max_cnt: Some(5),
+            cnt_period: LimitPeriod::Monthly,
+        };
+        /// The limits that checking accounts have unless configured otherwise.
+        pub const CHECKING_DEFAULT: WithdrawalLimits = WithdrawalLimits {
+            max_single_amount: Some(100_000.0),
+            max_daily_amount: Some(200_000.0),
+            max_cnt: Some(10),
+            cnt_period: LimitPeriod::Daily,
+        };
+
+        /// Prints the limits.
+        pub fn print(&self) {
+            let fmt_limit = |limit: Option<f64>| limit.map_or(String::from("None"), |l| l.to_string());
+
+            println!("Max Single Withdrawal: {}", fmt_limit(self.max_single_amount));
+            println!("Max Daily Withdrawal Total: {}", fmt_limit(self.max_daily_amount));
+            println!(
+                "Max Number of Withdrawals: {}",
+                self.max_cnt
+                    .map_or(String::from("None"), |c| format!("{c} ({})", self.cnt_period))
+            );
+        }
+    }
+
+    /// The withdrawal limits of each account type, keyed by the type's title.
+    pub type WithdrawalLimitTable = HashMap<&'static str, WithdrawalLimits>;
+
+    /// Creates the withdrawal limits that account types start with, as configured.
+    pub fn default_table() -> WithdrawalLimitTable {
+        let limits = &config::get().limits;
+
+        HashMap::from([
+            (ACCOUNT_TYPE_TITLES[0], limits.savings),
+            (ACCOUNT_TYPE_TITLES[1], limits.checking),
+            (ACCOUNT_TYPE_TITLES[2], limits.time_deposit),
+        ])
+    }
+
+    /// Prompts for an optional amount limit, keeping the current one if nothing is inputted.
+    ///
+    /// `None` is returned if the prompt is cancelled, and `Some(None)` if the limit is removed.
+    fn prompt_amount_limit(msg: &str, current: Option<f64>) -> Option<Option<f64>> {
+        prompt_valid(msg, |input| match input.to_uppercase().as_str() {
+            "" => Ok(current),
+            "NONE" => Ok(None),
+            input => match input.parse::<f64>() {
+                Ok(limit) if (f64::MIN_POSITIVE..=f64::MAX).contains(&limit) => Ok(Some(limit)),
+                _ => Err(String::from(
+                    "Limit must be a positive floating point number or \"None\"!",
+                )),
+            },
+        })
+    }
+
+    /// Changes the withdrawal limits of an account type.
+    ///
+    /// The user is prompted to choose the account type and input each of its limits. Inputting nothing keeps a limit
+    /// as is, while inputting "None" removes it.
+    pub fn configure(table: &mut WithdrawalLimitTable) {
+        // Loans cannot be withdrawn from, so they have no limits to configure.
+        let type_titles = &ACCOUNT_TYPE_TITLES[..3];
+
+        println!("Account Type Options:");
+        print_ordered_list(type_titles);
+
+        println!();
+
+        let Some(idx) = prompt_option(
+            "Account Type: ",
+            type_titles.len(),
+            "No account type with this ID exists!",
+        ) else {
+            return;
+        };
+        let title = type_titles[idx];
+        let mut limits = table.get(title).copied().unwrap_or(WithdrawalLimits::NONE);
+
+        println!();
+
+        println!("Current Limits (PHP):");
+        limits.print();
+
+        println!();
+
+        println!("Leave a limit blank to keep it, or input \"None\" to remove it.");
+
+        let Some(max_single_amount) = prompt_amount_limit("Max Single Withdrawal: ", limits.max_single_amount) else {
+            return;
+        };
+        let Some(max_daily_amount) = prompt_amount_limit("Max Daily Withdrawal Total: ", limits.max_daily_amount)
+        else {
+            return;
+        };
+        // Whether a new count was inputted, which also needs the period that it is counted over.
+        let Some((max_cnt, is_new_cnt)) = prompt_valid("Max Number of Withdrawals: ", |input| {
+            match input.to_uppercase().as_str() {
+                "" => Ok((limits.max_cnt, false)),
+                "NONE" => Ok((None, false)),
+                input => match input.parse::<usize>() {
+                    Ok(cnt) if cnt > 0 => Ok((Some(cnt), true)),
+                    _ => Err(String::from(
+                        "Number must be a positive whole number (integer) or \"None\"!",
+                    )),
+                },
+            }
+        }) else {
+            return;
+        };
+
+        if is_new_cnt {
+            println!();
+
+            println!("Period Options:");
+            print_ordered_list(&LIMIT_PERIOD_TITLES);
+
+            println!();
+
+            limits.cnt_period =
+                match prompt_option("Period: ", LIMIT_PERIOD_TITLES.len(), "No period with this ID exists!") {
+                    Some(0) => LimitPeriod::Daily,
+                    Some(_) => LimitPeriod::Monthly,
+                    None => return,
+                };
+        }
+
+        limits.max_single_amount = max_single_amount;
+        limits.max_daily_amount = max_daily_amount;
+        limits.max_cnt = max_cnt;
+
+        table.insert(title, limits);
+
+        println!();
+
+        println!("Updated Limits (PHP):");
+        limits.print();
+    }
+}
+
+mod standing_order {
+    use crate::{
+        Account, TransactionKind, currency, fx_position,
+        io_util::{notice, print_ordered_list, prompt_number, prompt_option, prompt_valid},
+        prompt_account_idx,
+        withdrawal_limit::WithdrawalLimitTable,
+    };
+    use chrono::{Datelike, Months, NaiveDate, TimeDelta};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    /// The titles of the kinds of transactions that can be scheduled.
+    const ORDER_KIND_TITLES: [&str; 3] = ["Deposit", "Withdrawal", "Transfer"];
+    /// The titles of how often a scheduled transaction can recur.
+    const RECURRENCE_TITLES: [&str; 2] = ["Monthly", "Every Number of Days"];
+    /// The longest interval that a scheduled transaction can recur every, in days.
+    const MAX_INTERVAL_DAYS: i64 = 3650;
+
+    /// The kinds of transactions that can be scheduled.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub enum OrderKind {
+        Deposit,
+        Withdrawal,
+        /// Moves balance from the account to another account.
+        Transfer {
+            /// The number of the account that receives the balance.
+            dest_number: String,
+        },
+    }
+
+    /// How often a scheduled transaction recurs.
+    #[derive(Clone, Copy, Deserialize, Serialize)]
+    pub enum Recurrence {
+        /// On a day of every month, or the month's last day if it is shorter.
+        Monthly { day: u32 },
+        /// After a fixed number of days.
+        Interval { days: i64 },
+    }
+    impl Recurrence {
+        /// Gets the first date that the transaction recurs on after a date.
+        pub fn next_after(&self, date: NaiveDate) -> NaiveDate {
+            match *self {
+                Recurrence::Monthly { day } => {
+                    let mut month_start = date.with_day(1).unwrap_or(date);
+
+                    loop {
+                        let next_month_start = month_start + Months::new(1);
+                        let last_day = (next_month_start - TimeDelta::days(1)).day();
+                        let candidate = month_start.with_day(day.min(last_day)).unwrap_or(month_start);
+
+                        if candidate > date {
+                            return candidate;
+                        }
+
+                        month_start = next_month_start;
+                    }
+                }
+                Recurrence::Interval { days } => date + TimeDelta::days(days),
+            }
+        }
+    }
+
+    /// A transaction that is executed on an account at regular intervals.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct StandingOrder {
+        /// The number that identifies the order among the account's orders.
+        pub id: usize,
+        /// The kind of transaction to execute.
+        pub kind: OrderKind,
+        /// The currency of the amount.
+        pub currency: String,
+        /// The amount to deposit, withdraw, or transfer.
+        pub amount: f64,
+        /// How often the transaction recurs.
+        pub recurrence: Recurrence,
+        /// The date that the transaction is next executed on.
+        pub next_date: NaiveDate,
+    }
+    impl StandingOrder {
+        /// Describes the order in a single line.
+        pub fn describe(&self) -> String {
+            let action = match &self.kind {
+                OrderKind::Deposit => String::from("Deposit"),
+                OrderKind::Withdrawal => String::from("Withdraw"),
+                OrderKind::Transfer { dest_number } => format!("Transfer to {dest_number}"),
+            };
+            let recurrence = match self.recurrence {
+                Recurrence::Monthly { day } => format!("on day {day} of every month"),
+                Recurrence::Interval { days } => format!("every {days} days"),
+            };
+
+            format!(
+                "#{} {action} {} {} {recurrence} (next on {})",
+                self.id, self.amount, self.currency, self.next_date
+            )
+        }
+    }
+
+    /// Schedules a recurring transaction on an account.
+    ///
+    /// The user is prompted to input the kind of transaction, its currency and amount, and how often it recurs. The
+    /// transaction is first executed on its first recurrence after today.
+    pub fn schedule(accounts: &mut [Account], src_idx: usize, today: NaiveDate) {
+        println!();
+
+        println!("Scheduled Transaction Options:");
+        print_ordered_list(&ORDER_KIND_TITLES);
+
+        println!();
+
+        let kind = match prompt_option(
+            "Transaction Type: ",
+            ORDER_KIND_TITLES.len(),
+            "No transaction type with this ID exists!",
+        ) {
+            Some(0) => OrderKind::Deposit,
+            Some(1) => OrderKind::Withdrawal,
+            Some(_) => {
+                println!();
+
+                println!("Destination Account");
+
+                let dest_idx = loop {
+                    let Some(dest_idx) = prompt_account_idx(accounts) else {
+                        return;
+                    };
+
+                    if dest_idx != src_idx {
+                        break dest_idx;
+                    }
+
+                    println!("Balance cannot be transferred to the same account!");
+
+                    println!();
+                };
+
+                OrderKind::Transfer {
+                    dest_number: accounts[dest_idx].number.clone(),
+                }
+            }
+            None => return,
+        };
+
+        println!();
+
+        let Some(currency) = currency::prompt_currency("Currency: ") else {
+            return;
+        };
+        let Some(amount) = prompt_number(
+            "Amount: ",
+            f64::MIN_POSITIVE..=f64::MAX,
+            "Amount must be a positive floating point number!",
+        ) else {
+            return;
+        };
+
+        println!();
+
+        println!("Recurrence Options:");
+        print_ordered_list(&RECURRENCE_TITLES);
+
+        println!();
+
+        let recurrence = match prompt_option(
+            "Recurrence: ",
+            RECURRENCE_TITLES.len(),
+            "No recurrence with this ID exists!",
+        ) {
+            Some(0) => match prompt_number(
+                "Day of the Month: ",
+                1..=31,
+                "Day must be a whole number (integer) from 1 to 31!",
+            ) {
+                Some(day) => Recurrence::Monthly { day },
+                None => return,
+            },
+            Some(_) => match prompt_number(
+                "Number of Days: ",
+                1..=MAX_INTERVAL_DAYS,
+                &format!("Number must be a whole number (integer) from 1 to {MAX_INTERVAL_DAYS}!"),
+            ) {
+                Some(days) => Recurrence::Interval { days },
+                None => return,
+            },
+            None => return,
+        };
+
+        let account = &mut accounts[src_idx];
+        let order = StandingOrder {
+            id: account.standing_orders.iter().map(|o| o.id).max().unwrap_or(0) + 1,
+            kind,
+            currency: currency.to_string(),
+            amount,
+            recurrence,
+            next_date: recurrence.next_after(today),
+        };
+
+        println!();
+
+        println!("Scheduled: {}", order.describe());
+
+        account.standing_orders.push(order);
+    }
+
+    /// Cancels one of an account's scheduled transactions.
+    ///
+    /// The user is prompted to choose the transaction from the account's scheduled transactions.
+    pub fn cancel(account: &mut Account) {
+        if account.standing_orders.is_empty() {
+            println!("No transactions are scheduled on this account!");
+
+            return;
+        }
+
+        println!();
+
+        println!("Scheduled Transactions:");
+
+        for order in &account.standing_orders {
+            println!("{}", order.describe());
+        }
+
+        println!();
+
+        let Some(idx) = prompt_valid("Scheduled Transaction ID: ", |input| {
+            match input.trim_start_matches('#').parse::<usize>() {
+                Ok(id) => account
+                    .standing_orders
+                    .iter()
+                    .position(|o| o.id == id)
+                    .ok_or_else(|| String::from("No scheduled transaction with this ID exists!")),
+                Err(_) => Err(String::from("ID must be a positive whole number (integer)!")),
+            }
+        }) else {
+            return;
+        };
+
+        let order = account.standing_orders.remove(idx);
+
+        println!("Cancelled: {}", order.describe());
+    }
+
+    /// Executes a scheduled transaction once, returning the updated balance of its account.
+    ///
+    /// Withdrawals and transfers from a time deposit before it matures fail instead of being charged the early
+    /// withdrawal penalty.
+    fn execute(
+        accounts: &mut [Account],
+        src_idx: usize,
+        order: &StandingOrder,
+        rates: &HashMap<&str, f64>,
+        limit_table: &WithdrawalLimitTable,
+        date: NaiveDate,
+    ) -> Result<f64, String> {
+        let amount = if order.currency == "PHP" {
+            order.amount
+        } else {
+            currency::convert(order.amount, &order.currency.as_str(), &"PHP", rates)
+        };
+
+        match &order.kind {
+            OrderKind::Deposit => {
+                accounts[src_idx].check_deposit()?;
+                accounts[src_idx].deposit_in(&order.currency, order.amount, rates, false, date);
+            }
+            OrderKind::Withdrawal => {
+                let limits = accounts[src_idx].withdrawal_limits(limit_table);
+
+                accounts[src_idx].check_withdrawal_allowed(date, &limits)?;
+                accounts[src_idx].check_withdrawal_limits(amount, date, &limits)?;
+
+                let charges = accounts[src_idx].withdrawal_charges(amount, date)?;
+
+                charges.check_no_penalty()?;
+
+                accounts[src_idx].post_withdrawal(TransactionKind::Withdrawal, amount, &charges);
+
+                fx_position::book(&order.currency, -order.amount, amount);
+            }
+            OrderKind::Transfer { dest_number } => {
+                let dest_idx = accounts
+                    .iter()
+                    .position(|a| !a.is_closed && a.number == *dest_number)
+                    .ok_or(format!("No open account with the number {dest_number} exists!"))?;
+
+                let limits = accounts[src_idx].withdrawal_limits(limit_table);
+
+                accounts[dest_idx].check_deposit()?;
+                accounts[src_idx].check_withdrawal_allowed(date, &limits)?;
+                accounts[src_idx].check_withdrawal_limits(amount, date, &limits)?;
+
+                let charges = accounts[src_idx].withdrawal_charges(amount, date)?;
+
+                charges.check_no_penalty()?;
+
+                accounts[src_idx].post_withdrawal(TransactionKind::TransferOut, amount, &charges);
+                accounts[dest_idx].post_deposit(TransactionKind::TransferIn, amount);
+            }
+        }
+
+        Ok(accounts[src_idx].balance)
+    }
+
+    /// Executes every scheduled transaction that is due on a date and reports the results.
+    ///
+    /// Transactions that were missed on earlier dates are caught up on. A failed transaction is skipped until its next
+    /// recurrence.
+    pub fn execute_due(
+        accounts: &mut [Account],
+        rates: &HashMap<&str, f64>,
+        limit_table: &WithdrawalLimitTable,
+        date: NaiveDate,
+    ) {
+        for src_idx in 0..accounts.len() {
+            if accounts[src_idx].is_closed {
+                continue;
+            }
+
+            for order_idx in 0..accounts[src_idx].standing_orders.len() {
+                while accounts[src_idx].standing_orders[order_idx].next_date <= date {
+                    let order = accounts[src_idx].standing_orders[order_idx].clone();
+
+                    notice(&match execute(accounts, src_idx, &order, rates, limit_table, date) {
+                        Ok(balance) => format!(
+                            "[{}] {}: {} (Updated Balance: {balance})",
+                            order.next_date,
+                            accounts[src_idx].number,
+                            order.describe()
+                        ),
+                        Err(reason) => format!(
+                            "[{}] {}: {} FAILED: {reason}",
+                            order.next_date,
+                            accounts[src_idx].number,
+                            order.describe()
+                        ),
+                    });
+
+                    let standing_order = &mut accounts[src_idx].standing_orders[order_idx];
+
+                    standing_order.next_date = standing_order.recurrence.next_after(standing_order.next_date);
+                }
+            }
+        }
+    }
+}
+
+mod storage {
+    use crate::{Account, Transaction, config, currency::CURRENCIES_CODES, fx_position::PositionBook};
+    use argon2::Argon2;
+    use chacha20poly1305::{
+        ChaCha20Poly1305, KeyInit, Nonce,
+        aead::{Aead, AeadCore, OsRng, rand_core::RngCore},
+    };
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Serialize};
+    use std::{
+        collections::HashMap,
+        error,
+        fmt::Write as _,
+        fs::{self, OpenOptions},
+        io::Write,
+        path::{Path, PathBuf},
+        sync::Mutex,
+    };
+
+    /// The bytes that start an encrypted data file, which are followed by its salt and then its encrypted contents.
+    const ENCRYPTED_MAGIC: &[u8] = b"MCO1ENC1";
+    /// The start of the first line of an encrypted journal file, which is followed by its salt and a passphrase check.
+    const ENCRYPTED_JOURNAL_HEADER: &str = "#MCO1ENC1";
+    /// The number of bytes in the salt that a file's key is derived with.
+    const SALT_LEN: usize = 16;
+    /// The number of bytes in the nonce that is prefixed to every encrypted value.
+    const NONCE_LEN: usize = 12;
+    /// The error for a value that fails to decrypt, as a wrong passphrase cannot be told apart from a corrupted file.
+    const DECRYPT_ERROR: &str = "Wrong passphrase, or the file is corrupted!";
+
+    /// The key that a file is encrypted with, as derived from the passphrase.
+    struct FileKey {
+        /// The random salt that the key was derived with, which is saved in the file.
+        salt: [u8; SALT_LEN],
+        /// The derived key, which is compared to check a passphrase without decrypting a file.
+        key: [u8; 32],
+        /// The cipher that values are encrypted and decrypted with under the key.
+        cipher: ChaCha20Poly1305,
+    }
+    impl FileKey {
+        /// Derives the key of a passphrase with a salt.
+        fn derive(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<FileKey, Box<dyn error::Error>> {
+            let mut key = [0u8; 32];
+
+            Argon2::default()
+                .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+                .map_err(|err| format!("Failed to derive the key: {err}"))?;
+
+            Ok(FileKey {
+                salt,
+                key,
+                cipher: ChaCha20Poly1305::new(&key.into()),
+            })
+        }
+
+        /// Derives the key of a passphrase with a new random salt.
+        fn generate(passphrase: &str) -> Result<FileKey, Box<dyn error::Error>> {
+            let mut salt = [0u8; SALT_LEN];
+
+            OsRng.fill_bytes(&mut salt);
+
+            FileKey::derive(passphrase, salt)
+        }
+
+        /// Encrypts a value, returning it prefixed with the random nonce that it was encrypted with.
+        fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn error::Error>> {
+            let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+            let ciphertext = self
+                .cipher
+                .encrypt(&nonce, plaintext)
+                .map_err(|_| "Failed to encrypt the file!")?;
+
+            Ok([nonce.as_slice(), &ciphertext].concat())
+        }
+
+        /// Decrypts a value encrypted by [`FileKey::encrypt`], failing if it was tampered with.
+        fn decrypt(&self, bytes: &[u8]) -> Result<Vec<u8>, Box<dyn error::Error>> {
+            if bytes.len() < NONCE_LEN {
+                return Err(DECRYPT_ERROR.into());
+            }
+
+            let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+
+            self.cipher
+                .decrypt(Nonce::from_slice(nonce), ciphertext)
+                .map_err(|_| DECRYPT_ERROR.into())
+        }
+    }
+
+    /// The keys of the data and journal files, which are unset while the files are in plaintext.
+    #[derive(Default)]
+    struct Keys {
+        /// The key of the data file.
+        data: Option<FileKey>,
+        /// The key of the journal file, which has its own salt.
+        journal: Option<FileKey>,
+    }
+
+    /// The keys of the files, once they are unlocked.
+    static KEYS: Mutex<Keys> = Mutex::new(Keys {
+        data: None,
+        journal: None,
+    });
+
+    /// Whether, and how, the data and journal files are encrypted.
+    pub enum Encryption {
+        /// The files are saved in plaintext.
+        Disabled,
+        /// The files are yet to be encrypted, so a new passphrase is needed.
+        New,
+        /// A file is already encrypted, so its passphrase is needed.
+        Existing,
+    }
+
+    /// Encodes bytes as lowercase hexadecimal.
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().fold(String::new(), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+
+            hex
+        })
+    }
+
+    /// Decodes hexadecimal encoded by [`to_hex`].
+    fn from_hex(hex: &str) -> Result<Vec<u8>, Box<dyn error::Error>> {
+        if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+            return Err(DECRYPT_ERROR.into());
+        }
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| DECRYPT_ERROR.into()))
+            .collect()
+    }
+
+    /// Reads the salt of an encrypted data file, which is `None` if the file is in plaintext.
+    fn data_salt(bytes: &[u8]) -> Result<Option<[u8; SALT_LEN]>, Box<dyn error::Error>> {
+        let Some(rest) = bytes.strip_prefix(ENCRYPTED_MAGIC) else {
+            return Ok(None);
+        };
+
+        match rest.get(..SALT_LEN).and_then(|salt| salt.try_into().ok()) {
+            Some(salt) => Ok(Some(salt)),
+            None => Err(DECRYPT_ERROR.into()),
+        }
+    }
+
+    /// The first line of an encrypted journal file.
+    struct JournalHeader {
+        /// The salt that the journal's key was derived with.
+        salt: [u8; SALT_LEN],
+        /// The encrypted [`ENCRYPTED_MAGIC`], which only decrypts with the right passphrase.
+        check: Vec<u8>,
+    }
+
+    /// Reads the header of an encrypted journal file, which is `None` if the file is in plaintext.
+    fn journal_header(contents: &str) -> Result<Option<JournalHeader>, Box<dyn error::Error>> {
+        let Some(header) = contents
+            .lines()
+            .next()
+            .and_then(|l| l.strip_prefix(ENCRYPTED_JOURNAL_HEADER))
+        else {
+            return Ok(None);
+        };
+        let mut fields = header.split_whitespace();
+        let salt = from_hex(fields.next().unwrap_or_default())?
+            .try_into()
+            .map_err(|_| DECRYPT_ERROR)?;
+        let check = from_hex(fields.next().unwrap_or_default())?;
+
+        Ok(Some(JournalHeader { salt, check }))
+    }
+
+    /// Renders the first line of a journal file encrypted with a key.
+    fn render_journal_header(key: &FileKey) -> Result<String, Box<dyn error::Error>> {
+        Ok(format!(
+            "{ENCRYPTED_JOURNAL_HEADER} {} {}",
+            to_hex(&key.salt),
+            to_hex(&key.encrypt(ENCRYPTED_MAGIC)?)
+        ))
+    }
+
+    /// Reads a file if it exists, or nothing otherwise.
+    fn read_if_exists(path: &Path) -> Result<Vec<u8>, Box<dyn error::Error>> {
+        Ok(if path.exists() { fs::read(path)? } else { Vec::new() })
+    }
+
+    /// Checks whether the data and journal files are encrypted, or are to be encrypted as configured.
+    pub fn encryption() -> Result<Encryption, Box<dyn error::Error>> {
+        let files = &config::get().files;
+
+        if let Some(path) = &files.data_file
+            && data_salt(&read_if_exists(path)?)?.is_some()
+        {
+            return Ok(Encryption::Existing);
+        }
+
+        if let Some(path) = &files.journal_file
+            && journal_header(&String::from_utf8_lossy(&read_if_exists(path)?))?.is_some()
+        {
+            return Ok(Encryption::Existing);
+        }
+
+        Ok(if files.encrypt {
+            Encryption::New
+        } else {
+            Encryption::Disabled
+        })
+    }
+
+    /// Derives the keys of the configured files from a passphrase, failing if it does not match an encrypted file.
+    ///
+    /// Files that are not encrypted yet are given keys with new salts.
+    fn open_keys(passphrase: &str) -> Result<Keys, Box<dyn error::Error>> {
+        let files = &config::get().files;
+        let mut keys = Keys::default();
+
+        if let Some(path) = &files.data_file {
+            let bytes = read_if_exists(path)?;
+
+            keys.data = Some(match data_salt(&bytes)? {
+                Some(salt) => {
+                    let key = FileKey::derive(passphrase, salt)?;
+
+                    key.decrypt(&bytes[ENCRYPTED_MAGIC.len() + SALT_LEN..])?;
+
+                    key
+                }
+                None => FileKey::generate(passphrase)?,
+            });
+        }
+
+        if let Some(path) = &files.journal_file {
+            let contents = String::from_utf8_lossy(&read_if_exists(path)?).into_owned();
+
+            keys.journal = Some(match journal_header(&contents)? {
+                Some(JournalHeader { salt, check }) => {
+                    let key = FileKey::derive(passphrase, salt)?;
+
+                    if key.decrypt(&check)? != ENCRYPTED_MAGIC {
+                        return Err(DECRYPT_ERROR.into());
+                    }
+
+                    key
+                }
+                None => FileKey::generate(passphrase)?,
+            });
+        }
+
+        Ok(keys)
+    }
+
+    /// Reads the entries of a journal file, decrypting them if the file is encrypted with a key.
+    fn read_journal(path: &Path, key: Option<&FileKey>) -> Result<Vec<String>, Box<dyn error::Error>> {
+        let contents = String::from_utf8_lossy(&read_if_exists(path)?).into_owned();
+
+        match key {
+            Some(key) if journal_header(&contents)?.is_some() => contents
+                .lines()
+                .skip(1)
+                .map(|line| Ok(String::from_utf8(key.decrypt(&from_hex(line)?)?)?))
+                .collect(),
+            _ => Ok(contents.lines().map(String::from).collect()),
+        }
+    }
+
+    /// Gets the path of the temporary file that a file is written to before it replaces the file.
+    fn tmp_path(path: &Path) -> PathBuf {
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+
+        file_name.push(".tmp");
+
+        path.with_file_name(file_name)
+    }
+
+    /// Renders the contents of a journal file with its entries encrypted with a key.
+    fn render_journal(key: &FileKey, entries: &[String]) -> Result<String, Box<dyn error::Error>> {
+        let mut contents = render_journal_header(key)?;
+
+        for entry in entries {
+            contents.push('\n');
+            contents.push_str(&to_hex(&key.encrypt(entry.as_bytes())?));
+        }
+
+        contents.push('\n');
+
+        Ok(contents)
+    }
+
+    /// Rewrites a journal file with its entries encrypted with a key.
+    ///
+    /// The entries are first written to a temporary file that then replaces the journal file.
+    fn write_journal(path: &Path, key: &FileKey, entries: &[String]) -> Result<(), Box<dyn error::Error>> {
+        let tmp_path = tmp_path(path);
+
+        fs::write(&tmp_path, render_journal(key, entries)?)?;
+        fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+
+    /// Unlocks the encryption of the data and journal files with a passphrase.
+    ///
+    /// A journal file still in plaintext is encrypted right away, while a data file still in plaintext is encrypted
+    /// when it is next saved.
+    pub fn unlock(passphrase: &str) -> Result<(), Box<dyn error::Error>> {
+        let keys = open_keys(passphrase)?;
+
+        if let (Some(path), Some(key)) = (&config::get().files.journal_file, &keys.journal)
+            && path.exists()
+            && journal_header(&String::from_utf8_lossy(&fs::read(path)?))?.is_none()
+        {
+            write_journal(path, key, &read_journal(path, None)?)?;
+        }
+
+        *KEYS.lock().unwrap_or_else(|e| e.into_inner()) = keys;
+
+        Ok(())
+    }
+
+    /// Checks whether a passphrase is the one that the files were unlocked with.
+    pub fn check_passphrase(passphrase: &str) -> Result<(), Box<dyn error::Error>> {
+        let keys = KEYS.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(key) = keys.data.as_ref().or(keys.journal.as_ref())
+            && FileKey::derive(passphrase, key.salt)?.key != key.key
+        {
+            return Err("Wrong passphrase!".into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the files are encrypted, which is only after they are unlocked.
+    pub fn is_unlocked() -> bool {
+        let keys = KEYS.lock().unwrap_or_else(|e| e.into_inner());
+
+        keys.data.is_some() || keys.journal.is_some()
+    }
+
+    /// A file written to its temporary path, waiting to replace the file.
+    struct StagedFile<'a> {
+        /// The path of the file.
+        path: &'a Path,
+        /// The contents of the file before it is replaced, which is `None` if it does not exist.
+        old_contents: Option<Vec<u8>>,
+    }
+
+    /// Writes the data and journal files encrypted with new keys to their temporary paths.
+    ///
+    /// The temporary files already written are returned even if a later one fails, so that they can be cleaned up.
+    fn stage_new_keys<'a>(
+        data: &BankDataRef,
+        keys: &Keys,
+        new_keys: &Keys,
+        staged_files: &mut Vec<StagedFile<'a>>,
+    ) -> Result<(), Box<dyn error::Error>> {
+        let files = &config::get().files;
+
+        if let Some(path) = &files.data_file {
+            fs::write(tmp_path(path), render_data(data, new_keys.data.as_ref())?)?;
+
+            staged_files.push(StagedFile {
+                path,
+                old_contents: path.exists().then(|| fs::read(path)).transpose()?,
+            });
+        }
+
+        if let (Some(path), Some(new_key)) = (&files.journal_file, &new_keys.journal)
+            && path.exists()
+        {
+            let entries = read_journal(path, keys.journal.as_ref())?;
+
+            fs::write(tmp_path(path), render_journal(new_key, &entries)?)?;
+
+            staged_files.push(StagedFile {
+                path,
+                old_contents: Some(fs::read(path)?),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Re-encrypts the data and journal files with a new passphrase.
+    ///
+    /// The bank's state is saved to the data file with the new key, and the journal file is rewritten with it. Both
+    /// files are written in full before either is replaced, and a file already replaced is restored if the other
+    /// fails, so that the files never end up encrypted with different passphrases.
+    pub fn change_passphrase(passphrase: &str, data: &BankDataRef) -> Result<(), Box<dyn error::Error>> {
+        let mut keys = KEYS.lock().unwrap_or_else(|e| e.into_inner());
+        let new_keys = Keys {
+            data: keys.data.as_ref().map(|_| FileKey::generate(passphrase)).transpose()?,
+            journal: keys
+                .journal
+                .as_ref()
+                .map(|_| FileKey::generate(passphrase))
+                .transpose()?,
+        };
+        let mut staged_files = Vec::<StagedFile>::new();
+
+        if let Err(err) = stage_new_keys(data, &keys, &new_keys, &mut staged_files) {
+            for file in &staged_files {
+                let _ = fs::remove_file(tmp_path(file.path));
+            }
+
+            return Err(err);
+        }
+
+        for (i, file) in staged_files.iter().enumerate() {
+            if let Err(err) = fs::rename(tmp_path(file.path), file.path) {
+                for file in &staged_files[i..] {
+                    let _ = fs::remove_file(tmp_path(file.path));
+                }
+
+                for file in &staged_files[..i] {
+                    match &file.old_contents {
+                        Some(contents) => fs::write(file.path, contents)?,
+                        None => fs::remove_file(file.path)?,
+                    }
+                }
+
+                return Err(err.into());
+            }
+        }
+
+        *keys = new_keys;
+
+        Ok(())
+    }
+
+    /// The state of the bank that is saved between sessions.
+    #[derive(Serialize, Deserialize)]
+    pub struct BankData {
+        /// Every registered account, including closed ones.
+        pub accounts: Vec<Account>,
+        /// The current value in PHP of each foreign currency.
+        pub exchange_rates: HashMap<String, f64>,
+        /// The dates that the exchange rates took effect.
+        pub exchange_rate_dates: HashMap<String, NaiveDate>,
+        /// The last date that the bank's daily processing was run for.
+        pub processed_date: NaiveDate,
+        /// The bank's holdings of each foreign currency.
+        #[serde(default)]
+        pub fx_positions: PositionBook,
+    }
+    impl BankData {
+        /// Copies the exchange rates and their dates into maps keyed by the known currency codes.
+        pub fn rates_into(&self, rates: &mut HashMap<&str, f64>, rate_dates: &mut HashMap<&str, NaiveDate>) {
+            for code in CURRENCIES_CODES.iter().skip(1) {
+                if let Some(&rate) = self.exchange_rates.get(*code) {
+                    rates.insert(code, rate);
+                }
+
+                if let Some(&date) = self.exchange_rate_dates.get(*code) {
+                    rate_dates.insert(code, date);
+                }
+            }
+        }
+    }
+
+    /// A transaction as appended to the journal file.
+    #[derive(Serialize)]
+    struct JournalEntry<'a> {
+        /// The number of the account that the transaction was made on.
+        account: &'a str,
+        /// The transaction itself.
+        #[serde(flatten)]
+        transaction: &'a Transaction,
+    }
+
+    /// Loads the bank's saved state from the data file, if one is configured and exists.
+    pub fn load_data() -> Result<Option<BankData>, Box<dyn error::Error>> {
+        let Some(path) = &config::get().files.data_file else {
+            return Ok(None);
+        };
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let bytes = fs::read(path)?;
+        let json = match data_salt(&bytes)? {
+            Some(_) => match &KEYS.lock().unwrap_or_else(|e| e.into_inner()).data {
+                Some(key) => key.decrypt(&bytes[ENCRYPTED_MAGIC.len() + SALT_LEN..])?,
+                None => return Err("The data file is encrypted, but it was not unlocked!".into()),
+            },
+            None => bytes,
+        };
+
+        Ok(Some(serde_json::from_slice(&json)?))
+    }
+
+    /// The state of the bank as borrowed for saving, which is serialized the same way as [`BankData`].
+    #[derive(Serialize)]
+    pub struct BankDataRef<'a> {
+        /// Every registered account, including closed ones.
+        pub accounts: &'a [Account],
+        /// The current value in PHP of each foreign currency.
+        pub exchange_rates: &'a HashMap<&'a str, f64>,
+        /// The dates that the exchange rates took effect.
+        pub exchange_rate_dates: &'a HashMap<&'a str, NaiveDate>,
+        /// The last date that the bank's daily processing was run for.
+        pub processed_date: NaiveDate,
+        /// The bank's holdings of each foreign currency.
+        pub fx_positions: &'a PositionBook,
+    }
+
+    /// Saves the bank's state to the data file, if one is configured.
+    ///
+    /// The state is first written to a temporary file that then replaces the data file, so that a failed save does not
+    /// corrupt the previous one. The state is encrypted if the files were unlocked.
+    pub fn save_data(data: &BankDataRef) -> Result<(), Box<dyn error::Error>> {
+        let Some(path) = &config::get().files.data_file else {
+            return Ok(());
+        };
+        let tmp_path = tmp_path(path);
+        let bytes = render_data(data, KEYS.lock().unwrap_or_else(|e| e.into_inner()).data.as_ref())?;
+
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+
+    /// Renders the contents of the data file, encrypted if a key is given.
+    fn render_data(data: &BankDataRef, key: Option<&FileKey>) -> Result<Vec<u8>, Box<dyn error::Error>> {
+        let json = serde_json::to_vec_pretty(data)?;
+
+        Ok(match key {
+            Some(key) => [ENCRYPTED_MAGIC, &key.salt, &key.encrypt(&json)?].concat(),
+            None => json,
+        })
+    }
+
+    /// The journal entries held back until the work that made them is committed, if entries are being held back.
+    static HELD_ENTRIES: Mutex<Option<Vec<(String, Transaction)>>> = Mutex::new(None);
+
+    /// Holds back the transactions journaled from now on until [`release_journal`] is called, so that work which may
+    /// be rolled back does not reach the journal.
+    pub fn hold_journal() {
+        *HELD_ENTRIES.lock().unwrap_or_else(|e| e.into_inner()) = Some(Vec::new());
+    }
+
+    /// Stops holding back transactions, appending the held ones to the journal file if their work was committed or
+    /// discarding them otherwise.
+    pub fn release_journal(is_committed: bool) -> Result<(), Box<dyn error::Error>> {
+        let entries = HELD_ENTRIES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .unwrap_or_default();
+
+        if is_committed {
+            for (account_number, transaction) in entries {
+                append_journal(&account_number, &transaction)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Appends a transaction made on an account to the journal file, if one is configured.
+    ///
+    /// If the files were unlocked, the transaction is encrypted on its own line. While transactions are held back, it
+    /// is only appended once it is released.
+    pub fn append_journal(account_number: &str, transaction: &Transaction) -> Result<(), Box<dyn error::Error>> {
+        let Some(path) = &config::get().files.journal_file else {
+            return Ok(());
+        };
+
+        if let Some(entries) = HELD_ENTRIES.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            entries.push((account_number.to_string(), transaction.clone()));
+
+            return Ok(());
+        }
+
+        let entry = JournalEntry {
+            account: account_number,
+            transaction,
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        if let Some(key) = &KEYS.lock().unwrap_or_else(|e| e.into_inner()).journal {
+            if file.metadata()?.len() == 0 {
+                writeln!(file, "{}", render_journal_header(key)?)?;
+            }
+
+            line = to_hex(&key.encrypt(line.as_bytes())?);
+        }
+
+        writeln!(file, "{line}")?;
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn file_key_round_trips() {
+            let key = FileKey::generate("correct horse").unwrap();
+            let ciphertext = key.encrypt(b"balance: 100").unwrap();
+
+            assert_ne!(&ciphertext[NONCE_LEN..], b"balance: 100");
+            assert_eq!(key.decrypt(&ciphertext).unwrap(), b"balance: 100");
+
+            // The same passphrase and salt derive the same key, as when a file is unlocked again.
+            let same_key = FileKey::derive("correct horse", key.salt).unwrap();
+
+            assert_eq!(same_key.decrypt(&ciphertext).unwrap(), b"balance: 100");
+        }
+
+        #[test]
+        fn wrong_passphrase_fails_to_decrypt() {
+            let key = FileKey::generate("correct horse").unwrap();
+            let ciphertext = key.encrypt(b"balance: 100").unwrap();
+            let wrong_key = FileKey::derive("battery staple", key.salt).unwrap();
+
+            assert_eq!(wrong_key.decrypt(&ciphertext).unwrap_err().to_string(), DECRYPT_ERROR);
+            assert_eq!(
+                key.decrypt(&ciphertext[..NONCE_LEN - 1]).unwrap_err().to_string(),
+                DECRYPT_ERROR
+            );
+        }
+
+        #[test]
+        fn tampered_ciphertext_fails_to_decrypt() {
+            let key = FileKey::generate("correct horse").unwrap();
+            let mut ciphertext = key.encrypt(b"balance: 100").unwrap();
+            let last_idx = ciphertext.len() - 1;
+
+            ciphertext[last_idx] ^= 1;
+
+            assert_eq!(key.decrypt(&ciphertext).unwrap_err().to_string(), DECRYPT_ERROR);
+        }
+
+        #[test]
+        fn hex_round_trips() {
+            assert_eq!(to_hex(&[0x00, 0xab, 0xff]), "00abff");
+            assert_eq!(from_hex("00abff").unwrap(), [0x00, 0xab, 0xff]);
+            assert!(from_hex("abc").is_err());
+            assert!(from_hex("zz").is_err());
+        }
+    }
+}
+
+mod rpc {
+    use crate::{
+        Account, AccountType, TransactionKind, batch, clock, config, currency, fx_position, io_util::notice,
+        process_days, storage, withdrawal_limit::WithdrawalLimitTable,
+    };
+    use chrono::{NaiveDate, TimeDelta};
+    use serde::{Deserialize, Serialize, de::DeserializeOwned};
+    use serde_json::{Value, json};
+    use std::{
+        collections::HashMap,
+        io::{self, BufRead, Write},
+    };
+
+    /// The error code for a line that is not valid JSON.
+    pub const PARSE_ERROR: i64 = -32700;
+    /// The error code for JSON that is not a JSON-RPC 2.0 request.
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// The error code for a method that does not exist.
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// The error code for parameters that are missing, of the wrong type, or out of range.
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// The error code for an account that does not exist, is closed, or cannot be told apart by its name.
+    pub const ACCOUNT_NOT_FOUND: i64 = -32001;
+    /// The error code for a currency that does not exist or is not enabled.
+    pub const CURRENCY_NOT_ENABLED: i64 = -32002;
+    /// The error code for a transaction that the account's rules reject, such as its type, balance or limits.
+    pub const TRANSACTION_REJECTED: i64 = -32003;
+
+    /// The bank's state that the methods act on.
+    pub struct Bank<'a> {
+        /// Every registered account, including closed ones.
+        pub accounts: &'a mut Vec<Account>,
+        /// The current value in PHP of each foreign currency.
+        pub exchange_rates: &'a mut HashMap<&'static str, f64>,
+        /// The dates that the exchange rates took effect.
+        pub exchange_rate_dates: &'a mut HashMap<&'static str, NaiveDate>,
+        /// The last date that the bank's daily processing was run for.
+        pub processed_date: &'a mut NaiveDate,
+        /// The withdrawal limits of each account type.
+        pub limit_table: &'a WithdrawalLimitTable,
+    }
+
+    /// A JSON-RPC 2.0 request, which is a notification when it has no ID.
+    #[derive(Deserialize)]
+    struct Request {
+        /// The protocol version, which must be "2.0".
+        jsonrpc: String,
+        /// The name of the method to call.
+        method: String,
+        /// The method's named parameters.
+        #[serde(default)]
+        params: Value,
+    }
+
+    /// A JSON-RPC 2.0 response, which has either a result or an error.
+    #[derive(Serialize)]
+    struct Response {
+        /// The protocol version, which is always "2.0".
+        jsonrpc: &'static str,
+        /// The ID of the request, or null if it could not be read.
+        id: Value,
+        /// The method's result, if it succeeded.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        result: Option<Value>,
+        /// Why the request failed, if it did.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<Error>,
+    }
+    impl Response {
+        /// Creates the response to a request.
+        fn new(id: Value, outcome: Result<Value, Error>) -> Response {
+            let (result, error) = match outcome {
+                Ok(result) => (Some(result), None),
+                Err(error) => (None, Some(error)),
+            };
+
+            Response {
+                jsonrpc: "2.0",
+                id,
+                result,
+                error,
+            }
+        }
+    }
+
+    /// Why a request failed.
+    #[derive(Serialize)]
+    struct Error {
+        /// The code that identifies the kind of failure.
+        code: i64,
+        /// The human-readable reason for the failure.
+        message: String,
+    }
+    impl Error {
+        /// Creates an error with a code and a message.
+        fn new(code: i64, message: impl Into<String>) -> Error {
+            Error {
+                code,
+                message: message.into(),
+            }
+        }
+    }
+
+    /// The parameters of the register method.
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct RegisterParams {
+        /// The name of the owner of the account.
+        name: String,
+        /// The account type, which is "savings", "checking" or "time_deposit".
+        #[serde(rename = "type")]
+        kind: String,
+        /// The term of a time deposit, in days.
+        term_days: Option<i64>,
+        /// The placement amount of a time deposit, in PHP.
+        amount: Option<f64>,
+    }
+
+    /// The parameters of the deposit method.
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct DepositParams {
+        /// The number or unique name of the account.
+        account: String,
+        /// The amount deposited, in the currency.
+        amount: f64,
+        /// The code of the currency deposited, which is the default currency if omitted.
+        currency: Option<String>,
+        /// Whether the deposit is made by cheque, which places it on hold.
+        #[serde(default)]
+        cheque: bool,
+    }
+
+    /// The parameters of the withdraw method.
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct WithdrawParams {
+        /// The number or unique name of the account.
+        account: String,
+        /// The amount withdrawn, in the currency.
+        amount: f64,
+        /// The code of the currency withdrawn, which is the default currency if omitted.
+        currency: Option<String>,
+        /// Whether to accept the penalty of withdrawing from a time deposit before it matures.
+        #[serde(default)]
+        allow_early: bool,
+    }
+
+    /// The parameters of the convert method.
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct ConvertParams {
+        /// The amount converted, in the source currency.
+        amount: f64,
+        /// The code of the source currency.
+        from: String,
+        /// The code of the destination currency.
+        to: String,
+    }
+
+    /// The parameters of the set_rate method.
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct SetRateParams {
+        /// The code of the foreign currency.
+        currency: String,
+        /// The value in PHP of one unit of the currency.
+        rate: f64,
+    }
+
+    /// The parameters of the interest_projection method.
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct InterestProjectionParams {
+        /// The number or unique name of the account.
+        account: String,
+        /// The number of days to project, up to [`Account::MAX_INTEREST_PROJECTION_DAYS`].
+        days: u32,
+    }
+
+    /// Serves requests read line by line from the standard input, writing each response as a line to the standard
+    /// output until the input ends.
+    ///
+    /// The bank's daily processing runs before each request, and every request that changes the bank's state is saved
+    /// right away. Notices of the processing are printed to the standard error.
+    pub fn serve(bank: &mut Bank) {
+        let mut stdout = io::stdout();
+
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let Some(response) = handle(bank, &line) else {
+                continue;
+            };
+            let Ok(response) = serde_json::to_string(&response) else {
+                continue;
+            };
+
+            if writeln!(stdout, "{response}").and_then(|_| stdout.flush()).is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Handles a line of input, returning the response to write, which notifications do not have.
+    fn handle(bank: &mut Bank, line: &str) -> Option<Response> {
+        let value = match serde_json::from_str::<Value>(line) {
+            Ok(value) => value,
+            Err(err) => {
+                return Some(Response::new(
+                    Value::Null,
+                    Err(Error::new(PARSE_ERROR, err.to_string())),
+                ));
+            }
+        };
+        let request = match Request::deserialize(&value) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            _ => {
+                let id = value.get("id").cloned().unwrap_or(Value::Null);
+
+                return Some(Response::new(
+                    id,
+                    Err(Error::new(
+                        INVALID_REQUEST,
+                        "Request must be a JSON-RPC 2.0 request object!",
+                    )),
+                ));
+            }
+        };
+        // The ID is read from the object itself, as an ID of null still expects a response, unlike a missing one.
+        let id = value.get("id").cloned();
+        let outcome = call(bank, &request.method, request.params);
+
+        id.map(|id| Response::new(id, outcome))
+    }
+
+    /// Calls a method with its parameters after running the bank's daily processing up to today, saving the bank's
+    /// state if either changed it.
+    fn call(bank: &mut Bank, method: &str, params: Value) -> Result<Value, Error> {
+        let prev_processed_date = *bank.processed_date;
+
+        process_days(
+            bank.accounts,
+            bank.exchange_rates,
+            bank.limit_table,
+            bank.processed_date,
+            clock::today(),
+        );
+
+        let is_processed = *bank.processed_date != prev_processed_date;
+        let result = match method {
+            "register" => register(bank, parse_params(params)?)?,
+            "deposit" => deposit(bank, parse_params(params)?)?,
+            "withdraw" => withdraw(bank, parse_params(params)?)?,
+            "convert" => convert(bank, parse_params(params)?)?,
+            "set_rate" => set_rate(bank, parse_params(params)?)?,
+            "interest_projection" => interest_projection(bank, parse_params(params)?)?,
+            _ => {
+                return Err(Error::new(METHOD_NOT_FOUND, format!("No method \"{method}\" exists!")));
+            }
+        };
+
+        if is_processed || matches!(method, "register" | "deposit" | "withdraw" | "convert" | "set_rate") {
+            save(bank);
+        }
+
+        Ok(result)
+    }
+
+    /// Saves the bank's state to the data file, if one is configured.
+    fn save(bank: &Bank) {
+        if let Err(err) = storage::save_data(&storage::BankDataRef {
+            accounts: bank.accounts,
+            exchange_rates: bank.exchange_rates,
+            exchange_rate_dates: bank.exchange_rate_dates,
+            processed_date: *bank.processed_date,
+            fx_positions: &fx_position::snapshot(),
+        }) {
+            notice(&format!("Warning: Failed to save the data file: {err}"));
+        }
+    }
+
+    /// Reads the parameters of a method, where omitted parameters are read as an empty object.
+    fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, Error> {
+        let params = if params.is_null() { json!({}) } else { params };
+
+        serde_json::from_value(params).map_err(|err| Error::new(INVALID_PARAMS, err.to_string()))
+    }
+
+    /// Finds the open account with a number, or with a name that no other open account shares.
+    fn find_account_idx(accounts: &[Account], query: &str) -> Result<usize, Error> {
+        batch::find_account_idx(accounts, query).map_err(|reason| Error::new(ACCOUNT_NOT_FOUND, reason))
+    }
+
+    /// Finds an enabled currency by its code, or the default currency if no code was given.
+    fn find_currency(code: Option<&str>) -> Result<&'static str, Error> {
+        let code = code.unwrap_or(&config::get().currency.default).trim().to_uppercase();
+
+        match currency::CURRENCIES_CODES.iter().find(|&&c| c == code) {
+            Some(&code) if currency::is_enabled(code) => Ok(code),
+            Some(_) => Err(Error::new(
+                CURRENCY_NOT_ENABLED,
+                format!("Currency {code} is not enabled!"),
+            )),
+            None => Err(Error::new(
+                CURRENCY_NOT_ENABLED,
+                format!("No currency with the code \"{code}\" exists!"),
+            )),
+        }
+    }
+
+    /// Checks that an amount is a positive number.
+    fn check_amount(amount: f64) -> Result<f64, Error> {
+        if amount.is_finite() && amount > 0.0 {
+            Ok(amount)
+        } else {
+            Err(Error::new(INVALID_PARAMS, "Amount must be a positive number!"))
+        }
+    }
+
+    /// Registers a new account, placing the amount of a time deposit right away.
+    fn register(bank: &mut Bank, params: RegisterParams) -> Result<Value, Error> {
+        if params.name.trim().is_empty() {
+            return Err(Error::new(INVALID_PARAMS, "Account name must not be empty!"));
+        }
+
+        let kind = match (params.kind.as_str(), params.term_days, params.amount) {
+            ("savings", None, None) => AccountType::Savings,
+            ("checking", None, None) => AccountType::Checking,
+            ("time_deposit", Some(term_days), Some(_)) => {
+                if !AccountType::TIME_DEPOSIT_TERMS_DAYS.contains(&term_days) {
+                    return Err(Error::new(
+                        INVALID_PARAMS,
+                        format!("Term must be one of {:?} days!", AccountType::TIME_DEPOSIT_TERMS_DAYS),
+                    ));
+                }
+
+                AccountType::TimeDeposit {
+                    term_days,
+                    maturity_date: clock::today() + TimeDelta::days(term_days),
+                }
+            }
+            ("time_deposit", ..) => {
+                return Err(Error::new(
+                    INVALID_PARAMS,
+                    "Time deposits need a term_days and an amount!",
+                ));
+            }
+            ("savings" | "checking", ..) => {
+                return Err(Error::new(
+                    INVALID_PARAMS,
+                    "Only time deposits take a term_days and an amount!",
+                ));
+            }
+            (kind, ..) => {
+                return Err(Error::new(
+                    INVALID_PARAMS,
+                    format!("No account type \"{kind}\" exists, use savings, checking or time_deposit!"),
+                ));
+            }
+        };
+        let mut account = Account::new(bank.accounts.len() + 1, params.name, kind);
+
+        if let Some(amount) = params.amount {
+            account.balance = check_amount(amount)?;
+
+            account.record_transaction(TransactionKind::Deposit, amount);
+        }
+
+        let result = json!({
+            "account": account.number,
+            "name": account.name,
+            "type": account.kind.title(),
+            "balance": account.balance,
+            "maturity_date": match account.kind {
+                AccountType::TimeDeposit { maturity_date, .. } => Some(maturity_date),
+                _ => None,
+            },
+        });
+
+        bank.accounts.push(account);
+
+        Ok(result)
+    }
+
+    /// Deposits an amount into an account, placing it on hold if it needs to clear.
+    fn deposit(bank: &mut Bank, params: DepositParams) -> Result<Value, Error> {
+        let idx = find_account_idx(bank.accounts, &params.account)?;
+        let currency = find_currency(params.currency.as_deref())?;
+        let input_amount = check_amount(params.amount)?;
+        let account = &mut bank.accounts[idx];
+
+        account
+            .check_deposit()
+            .map_err(|reason| Error::new(TRANSACTION_REJECTED, reason))?;
+
+        let (reference, amount, hold) = account.deposit_in(
+            currency,
+            input_amount,
+            bank.exchange_rates,
+            params.cheque,
+            clock::today(),
+        );
+
+        Ok(json!({
+            "reference": reference,
+            "amount": amount,
+            "balance": account.balance,
+            "available_balance": account.available_balance(),
+            "hold": hold.map(|h| json!({ "amount": h.amount, "release_date": h.release_date })),
+        }))
+    }
+
+    /// Withdraws an amount from an account, within its withdrawal limits.
+    fn withdraw(bank: &mut Bank, params: WithdrawParams) -> Result<Value, Error> {
+        let idx = find_account_idx(bank.accounts, &params.account)?;
+        let currency = find_currency(params.currency.as_deref())?;
+        let input_amount = check_amount(params.amount)?;
+        let account = &mut bank.accounts[idx];
+        let today = clock::today();
+        let limits = account.withdrawal_limits(bank.limit_table);
+        let amount = if currency == "PHP" {
+            input_amount
+        } else {
+            currency::convert(input_amount, &currency, &"PHP", bank.exchange_rates)
+        };
+        let charges = account
+            .check_withdrawal_allowed(today, &limits)
+            .and_then(|_| account.check_withdrawal_limits(amount, today, &limits))
+            .and_then(|_| account.withdrawal_charges(amount, today))
+            .map_err(|reason| Error::new(TRANSACTION_REJECTED, reason))?;
+
+        if charges.penalty > 0.0 && !params.allow_early {
+            return Err(Error::new(
+                TRANSACTION_REJECTED,
+                format!(
+                    "Withdrawing before maturity incurs a penalty of {}, set allow_early to accept it!",
+                    charges.penalty
+                ),
+            ));
+        }
+
+        let reference = account.post_withdrawal(TransactionKind::Withdrawal, amount, &charges);
+
+        fx_position::book(currency, -input_amount, amount);
+
+        Ok(json!({
+            "reference": reference,
+            "amount": amount,
+            "penalty": charges.penalty,
+            "overdraft_fee": charges.overdraft_fee,
+            "balance": account.balance,
+            "available_balance": account.available_balance(),
+        }))
+    }
+
+    /// Exchanges an amount between two currencies at the current exchange rates.
+    fn convert(bank: &mut Bank, params: ConvertParams) -> Result<Value, Error> {
+        let src = find_currency(Some(&params.from))?;
+        let dest = find_currency(Some(&params.to))?;
+        let amount = check_amount(params.amount)?;
+        let dest_amount = currency::convert(amount, &src, &dest, bank.exchange_rates);
+        let php_amount = currency::convert(amount, &src, &"PHP", bank.exchange_rates);
+
+        fx_position::book(src, amount, php_amount);
+        fx_position::book(dest, -dest_amount, php_amount);
+
+        Ok(json!({
+            "amount": dest_amount,
+            "currency": dest,
+        }))
+    }
+
+    /// Records the exchange rate of a foreign currency, in effect from today.
+    fn set_rate(bank: &mut Bank, params: SetRateParams) -> Result<Value, Error> {
+        let code = find_currency(Some(&params.currency))?;
+
+        if code == "PHP" {
+            return Err(Error::new(INVALID_PARAMS, "PHP is the base currency and has no rate!"));
+        }
+
+        if !params.rate.is_finite() || params.rate <= 0.0 {
+            return Err(Error::new(INVALID_PARAMS, "Exchange rate must be a positive number!"));
+        }
+
+        let today = clock::today();
+
+        bank.exchange_rates.insert(code, params.rate);
+        bank.exchange_rate_dates.insert(code, today);
+
+        Ok(json!({ "currency": code, "rate": params.rate, "date": today }))
+    }
+
+    /// Projects the balance of an account over a number of days of interest.
+    fn interest_projection(bank: &mut Bank, params: InterestProjectionParams) -> Result<Value, Error> {
+        let account = &bank.accounts[find_account_idx(bank.accounts, &params.account)?];
+
+        if let AccountType::Loan(_) = account.kind {
+            return Err(Error::new(
+                TRANSACTION_REJECTED,
+                "Loans follow their amortization schedule instead!",
+            ));
+        }
+
+        if params.days > Account::MAX_INTEREST_PROJECTION_DAYS {
+            return Err(Error::new(
+                INVALID_PARAMS,
+                format!("Days must be at most {}!", Account::MAX_INTEREST_PROJECTION_DAYS),
+            ));
+        }
+
+        let (daily_interest, balances) = account.project_interest(params.days);
+
+        Ok(json!({
+            "balance": account.balance,
+            "currency": account.currency,
+            "annual_interest_rate": account.current_annual_interest_rate(),
+            "daily_interest": daily_interest,
+            "balances": balances,
+        }))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::withdrawal_limit;
+
+        /// Handles a line with an empty bank, returning the response as JSON.
+        fn handle_line(line: &str) -> Option<Value> {
+            let mut accounts = Vec::<Account>::new();
+            let mut exchange_rates = HashMap::from([("USD", 56.0)]);
+            let mut exchange_rate_dates = HashMap::new();
+            let mut processed_date = clock::today();
+            let limit_table = withdrawal_limit::default_table();
+            let mut bank = Bank {
+                accounts: &mut accounts,
+                exchange_rates: &mut exchange_rates,
+                exchange_rate_dates: &mut exchange_rate_dates,
+                processed_date: &mut processed_date,
+                limit_table: &limit_table,
+            };
+
+            handle(&mut bank, line).map(|response| serde_json::to_value(response).unwrap())
+        }
+
+        #[test]
+        fn malformed_json_is_a_parse_error() {
+            let response = handle_line("{\"jsonrpc\": \"2.0\",").unwrap();
+
+            assert_eq!(response["id"], Value::Null);
+            assert_eq!(response["error"]["code"], PARSE_ERROR);
+        }
+
+        #[test]
+        fn non_request_is_an_invalid_request() {
+            let response = handle_line(r#"{"jsonrpc": "1.0", "id": 7, "method": "convert"}"#).unwrap();
+
+            assert_eq!(response["id"], 7);
+            assert_eq!(response["error"]["code"], INVALID_REQUEST);
+
+            let response = handle_line("[1, 2]").unwrap();
+
+            assert_eq!(response["id"], Value::Null);
+            assert_eq!(response["error"]["code"], INVALID_REQUEST);
+        }
+
+        #[test]
+        fn notification_has_no_response() {
+            assert!(handle_line(r#"{"jsonrpc": "2.0", "method": "missing"}"#).is_none());
+            assert!(handle_line(r#"{"jsonrpc": "2.0", "method": "register", "params": {"name": ""}}"#).is_none());
+        }
+
+        #[test]
+        fn null_id_has_a_response() {
+            let response = handle_line(r#"{"jsonrpc": "2.0", "id": null, "method": "missing"}"#).unwrap();
+
+            assert_eq!(response["id"], Value::Null);
+            assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+            assert!(response.get("result").is_none());
+        }
+
+        #[test]
+        fn request_with_id_has_its_result() {
+            let response = handle_line(
+                r#"{"jsonrpc": "2.0", "id": "a", "method": "interest_projection", "params": {"account": "Ann", "days": 30}}"#,
+            )
+            .unwrap();
+
+            assert_eq!(response["id"], "a");
+            assert_eq!(response["error"]["code"], ACCOUNT_NOT_FOUND);
+
+            let response = handle_line(
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "set_rate", "params": {"currency": "USD", "rate": 57}}"#,
+            )
+            .unwrap();
+
+            assert_eq!(response["id"], 1);
+            assert!(response.get("error").is_none());
+        }
+    }
+}
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeDelta};
+use io_util::{
+    confirm, notice, print_ordered_list, prompt_number, prompt_option, prompt_secret, prompt_valid,
+    prompt_valid_completing,
+};
+use loan::Loan;
+use rate_provider::RateFeed;
+use receipt::Receipt;
+use savings_goal::SavingsGoal;
+use serde::{Deserialize, Serialize};
+use standing_order::StandingOrder;
+use std::{collections::HashMap, env, fmt};
+use withdrawal_limit::{LimitPeriod, WithdrawalLimitTable, WithdrawalLimits};
+
+/// The least number of characters in a passphrase that the data and journal files are encrypted with.
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// The environment variable that holds the passphrase of the encrypted files in JSON-RPC mode, which cannot prompt.
+const PASSPHRASE_ENV_VAR: &str = "BANK_PASSPHRASE";
+/// The titles of the available transactional procedures.
+const TRANSACTION_TITLES: [&str; 30] = [
+    "Register Account Name",
+    "Deposit Amount",
+    "Withdraw Amount",
+    "Currency Exchange",
+    "Record Exchange Rates",
+    "Show Interest Amount",
+    "Import Exchange Rates",
+    "Export Exchange Rates",
+    "Check Rate Sheet Consistency",
+    "List Accounts",
+    "Close Account",
+    "Set Overdraft Limit",
+    "Pay Loan Installment",
+    "Quote Loan Payoff",
+    "Schedule Transaction",
+    "Cancel Scheduled Transaction",
+    "Advance Bank Clock",
+    "Set Withdrawal Limits",
+    "Export Account Statement",
+    "Process Batch File",
+    "Export Account Activity",
+    "Reconcile Account Statement",
+    "Request Exchange Quote",
+    "Execute Exchange Quote",
+    "Show FX Positions",
+    "Compare Interest Scenarios",
+    "Set Savings Goal",
+    "Remove Savings Goal",
+    "Show Savings Goals",
+    "Change Passphrase",
+];
+
+/// The kinds of changes to an account's balance.
+#[derive(PartialEq, Clone, Copy, Deserialize, Serialize)]
+enum TransactionKind {
+    Deposit,
+    Withdrawal,
+    /// The withdrawal of the remaining balance when an account is closed.
+    Payout,
+    /// The interest credited to an account.
+    Interest,
+    /// The fee charged for withdrawing from a time deposit before it matures.
+    Penalty,
+    /// The fee charged for a withdrawal that overdraws an account.
+    OverdraftFee,
+    /// The interest charged on an overdrawn balance.
+    OverdraftInterest,
+    /// The principal lent out when a loan is granted.
+    LoanDisbursement,
+    /// The interest charged on a loan's principal.
+    LoanInterest,
+    /// The fee charged for paying a loan installment after its grace period.
+    LatePaymentPenalty,
+    /// A payment towards a loan.
+    LoanPayment,
+    /// Balance moved in from another account.
+    TransferIn,
+    /// Balance moved out to another account.
+    TransferOut,
+    /// A correction posted while reconciling against an external statement.
+    Adjustment,
+}
+impl fmt::Display for TransactionKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self {
+            TransactionKind::Deposit => "Deposit",
+            TransactionKind::Withdrawal => "Withdrawal",
+            TransactionKind::Payout => "Payout",
+            TransactionKind::Interest => "Interest",
+            TransactionKind::Penalty => "Penalty",
+            TransactionKind::OverdraftFee => "Overdraft Fee",
+            TransactionKind::OverdraftInterest => "Overdraft Interest",
+            TransactionKind::LoanDisbursement => "Loan Disbursement",
+            TransactionKind::LoanInterest => "Loan Interest",
+            TransactionKind::LatePaymentPenalty => "Late Payment Penalty",
+            TransactionKind::LoanPayment => "Loan Payment",
+            TransactionKind::TransferIn => "Transfer In",
+            TransactionKind::TransferOut => "Transfer Out",
+            TransactionKind::Adjustment => "Adjustment",
+        };
+
+        f.pad(label)
+    }
+}
+
+/// A record of a change to an account's balance.
+#[derive(Clone, Deserialize, Serialize)]
+struct Transaction {
+    /// The unique reference number of the change.
+    reference: String,
+    /// The kind of change made.
+    kind: TransactionKind,
+    /// The amount added to or taken from the balance, in the account's currency.
+    amount: f64,
+    /// The balance of the account after the change.
+    balance: f64,
+    /// When the change was made.
+    timestamp: DateTime<Local>,
+}
+
+/// The titles of the products that an account can be registered as.
+const ACCOUNT_TYPE_TITLES: [&str; 4] = ["Savings", "Checking", "Time Deposit", "Loan"];
+
+/// The products that an account can be registered as, each with its own rules.
+#[derive(Clone, Copy, PartialEq, Deserialize, Serialize)]
+enum AccountType {
+    /// Earns interest, but only allows a limited number of withdrawals per month.
+    Savings,
+    /// Earns no interest, but allows the balance to be overdrawn.
+    Checking,
+    /// Earns a higher interest over a fixed term that automatically rolls over, but penalizes early withdrawals.
+    TimeDeposit {
+        /// The number of days in a term.
+        term_days: i64,
+        /// The date when the current term ends.
+        maturity_date: NaiveDate,
+    },
+    /// Lends out a principal that is repaid in monthly installments, with the balance being the negated amount owed.
+    Loan(Loan),
+}
+impl AccountType {
+    /// The annual interest rate percentage of savings accounts, unless configured otherwise.
+    const SAVINGS_ANNUAL_INTEREST_RATE: f64 = 0.05;
+    /// How far below zero the balance of checking accounts can go, unless changed for the account.
+    const CHECKING_DEFAULT_OVERDRAFT_LIMIT: f64 = 10_000.0;
+    /// The flat fee charged for each withdrawal that overdraws a checking account.
+    const OVERDRAFT_FEE: f64 = 100.0;
+    /// The annual interest rate percentage charged daily on overdrawn balances, unless configured otherwise.
+    const OVERDRAFT_ANNUAL_INTEREST_RATE: f64 = 0.18;
+    /// The annual interest rate percentage of time deposits, unless configured otherwise.
+    const TIME_DEPOSIT_ANNUAL_INTEREST_RATE: f64 = 0.06;
+    /// The percentage of the amount withdrawn from a time deposit before maturity that is charged as a penalty.
+    const TIME_DEPOSIT_EARLY_WITHDRAWAL_PENALTY_RATE: f64 = 0.02;
+    /// The number of days in each of the terms that time deposits can be placed for.
+    const TIME_DEPOSIT_TERMS_DAYS: [i64; 4] = [30, 90, 180, 365];
+
+    /// Gets the title of the product.
+    fn title(&self) -> &'static str {
+        match self {
+            AccountType::Savings => ACCOUNT_TYPE_TITLES[0],
+            AccountType::Checking => ACCOUNT_TYPE_TITLES[1],
+            AccountType::TimeDeposit { .. } => ACCOUNT_TYPE_TITLES[2],
+            AccountType::Loan(_) => ACCOUNT_TYPE_TITLES[3],
+        }
+    }
+
+    /// Gets the annual interest rate percentage that the product earns.
+    fn annual_interest_rate(&self) -> f64 {
+        match self {
+            AccountType::Savings => config::get().interest.savings_annual_rate,
+            AccountType::Checking => 0.0,
+            AccountType::TimeDeposit { .. } => config::get().interest.time_deposit_annual_rate,
+            AccountType::Loan(loan) => loan.annual_interest_rate,
+        }
+    }
+}
+impl fmt::Display for AccountType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.title())
+    }
+}
+
+/// A deposited amount that is credited to the ledger balance but cannot be spent until it clears.
+#[derive(Clone, Copy, Deserialize, Serialize)]
+struct Hold {
+    /// The amount held, in the account's currency.
+    amount: f64,
+    /// The date when the amount clears and becomes available.
+    release_date: NaiveDate,
+}
+impl Hold {
+    /// The number of days that cheque deposits take to clear.
+    const CHEQUE_CLEARING_DAYS: i64 = 3;
+    /// The PHP equivalent from which foreign currency deposits are held.
+    const LARGE_FOREIGN_DEPOSIT_THRESHOLD: f64 = 50_000.0;
+    /// The number of days that large foreign currency deposits take to clear.
+    const LARGE_FOREIGN_DEPOSIT_CLEARING_DAYS: i64 = 2;
+
+    /// Creates the hold placed on a deposit of an amount, in PHP, made on a date, if the deposit needs to clear.
+    fn for_deposit(amount: f64, currency: &str, is_cheque: bool, date: NaiveDate) -> Option<Hold> {
+        let clearing_days = if is_cheque {
+            Hold::CHEQUE_CLEARING_DAYS
+        } else if currency != "PHP" && amount >= Hold::LARGE_FOREIGN_DEPOSIT_THRESHOLD {
+            Hold::LARGE_FOREIGN_DEPOSIT_CLEARING_DAYS
+        } else {
+            return None;
+        };
+
+        Some(Hold {
+            amount,
+            release_date: date + TimeDelta::days(clearing_days),
+        })
+    }
+}
+
+/// The charges that a withdrawal incurs on top of its amount.
+struct WithdrawalCharges {
+    /// The penalty for withdrawing from a time deposit before it matures.
+    penalty: f64,
+    /// The fee for overdrawing the account.
+    overdraft_fee: f64,
+}
+impl WithdrawalCharges {
+    /// Checks that a withdrawal is not charged an early withdrawal penalty, for withdrawals that no one is present to
+    /// confirm the penalty of.
+    fn check_no_penalty(&self) -> Result<(), String> {
+        if self.penalty > 0.0 {
+            Err(format!(
+                "Withdrawing before maturity incurs a penalty of {}!",
+                self.penalty
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A simple user bank account.
+#[derive(Clone, Deserialize, Serialize)]
+struct Account {
+    /// The unique number that identifies the account.
+    number: String,
+    /// The name of the owner of the account, which several accounts can share.
+    name: String,
+    /// The product that the account was registered as.
+    kind: AccountType,
+    /// The current balance of the account, which is negative when overdrawn.
+    balance: f64,
+    /// How far below zero the balance can go, which is zero if the account cannot be overdrawn.
+    overdraft_limit: f64,
+    /// The savings interest accrued daily since it was last credited.
+    accrued_interest: f64,
+    /// The currency that the account's balance is based on.
+    currency: String,
+    /// Whether the account was closed, which keeps it (and its history) from being used in transactions.
+    is_closed: bool,
+    /// The history of changes to the account's balance, from oldest to newest.
+    transactions: Vec<Transaction>,
+    /// The transactions scheduled to recur on the account.
+    standing_orders: Vec<StandingOrder>,
+    /// The deposits that have yet to clear.
+    holds: Vec<Hold>,
+    /// The amounts that the owner is saving toward.
+    #[serde(default)]
+    goals: Vec<SavingsGoal>,
+}
+impl Account {
+    /// The most days that the interest on a balance can be projected over.
+    const MAX_INTEREST_PROJECTION_DAYS: u32 = 36500;
+
+    /// Creates a new account with the default values.
+    ///
+    /// The account's number is generated from the sequence number of its registration.
+    fn new(seq: usize, name: String, kind: AccountType) -> Account {
+        Account {
+            number: account_number::generate(seq),
+            name,
+            kind,
+            balance: 0.0,
+            overdraft_limit: if kind == AccountType::Checking {
+                AccountType::CHECKING_DEFAULT_OVERDRAFT_LIMIT
+            } else {
+                0.0
+            },
+            accrued_interest: 0.0,
+            currency: String::from("PHP"),
+            is_closed: false,
+            transactions: Vec::new(),
+            standing_orders: Vec::new(),
+            holds: Vec::new(),
+            goals: Vec::new(),
+        }
+    }
+
+    /// Registers a new account.
+    ///
+    /// The user is prompted to input the owner's name and the account's type. Time deposits are also prompted for their
+    /// term and placement amount, which is deposited right away, while loans are prompted for their terms and disbursed
+    /// right away.
+    fn register(seq: usize) -> Option<Account> {
+        let name = prompt_valid("Account Name: ", |input| match input {
+            "" => Err(String::from("Account name must not be empty!")),
+            name => Ok(name.to_string()),
+        })?;
+
+        println!();
+
+        println!("Account Type Options:");
+        print_ordered_list(&ACCOUNT_TYPE_TITLES);
+
+        println!();
+
+        let kind = match prompt_option(
+            "Account Type: ",
+            ACCOUNT_TYPE_TITLES.len(),
+            "No account type with this ID exists!",
+        )? {
+            0 => AccountType::Savings,
+            1 => AccountType::Checking,
+            2 => {
+                println!();
+
+                println!("Term Options:");
+                print_ordered_list(&AccountType::TIME_DEPOSIT_TERMS_DAYS.map(|d| format!("{d} Days")));
+
+                println!();
+
+                let term_days = AccountType::TIME_DEPOSIT_TERMS_DAYS[prompt_option(
+                    "Term: ",
+                    AccountType::TIME_DEPOSIT_TERMS_DAYS.len(),
+                    "No term with this ID exists!",
+                )?];
+
+                AccountType::TimeDeposit {
+                    term_days,
+                    maturity_date: clock::today() + TimeDelta::days(term_days),
+                }
+            }
+            _ => {
+                println!();
+
+                AccountType::Loan(Loan::prompt_terms()?)
+            }
+        };
+
+        let mut account = Account::new(seq, name, kind);
+
+        if let AccountType::TimeDeposit { maturity_date, .. } = kind {
+            let amount = prompt_number(
+                "Placement Amount: ",
+                f64::MIN_POSITIVE..=f64::MAX,
+                "Placement amount must be a positive floating point number!",
+            )?;
+
+            account.balance = amount;
+
+            account.record_transaction(TransactionKind::Deposit, amount);
+
+            println!();
+
+            println!("Maturity Date: {maturity_date}");
+        }
+
+        if let AccountType::Loan(loan) = kind {
+            account.balance = -loan.principal;
+
+            account.record_transaction(TransactionKind::LoanDisbursement, loan.principal);
+
+            if let Some(installment) = loan.next_installment() {
+                println!();
+
+                println!(
+                    "First Installment: {} (due on {})",
+                    installment.payment, installment.due_date
+                );
+            }
+        }
+
+        Some(account)
+    }
+
+    /// Adds a change to the account's balance to its history, returning the change's reference number.
+    fn record_transaction(&mut self, kind: TransactionKind, amount: f64) -> String {
+        let reference = receipt::next_reference();
+
+        let transaction = Transaction {
+            reference: reference.clone(),
+            kind,
+            amount,
+            balance: self.balance,
+            timestamp: clock::now(),
+        };
+
+        if let Err(err) = storage::append_journal(&self.number, &transaction) {
+            notice(&format!("Warning: Failed to journal transaction {reference}: {err}"));
+        }
+
+        self.transactions.push(transaction);
+
+        reference
+    }
+
+    /// Creates the receipt of a transaction made on the account with an amount given in some currency.
+    fn receipt(
+        &self,
+        reference: String,
+        kind: TransactionKind,
+        currency: &str,
+        amount: f64,
+        rates: &HashMap<&str, f64>,
+        charges: f64,
+    ) -> Receipt {
+        let rate = rates.get(currency).copied().unwrap_or(1.0);
+
+        Receipt {
+            reference,
+            timestamp: clock::now(),
+            account_number: self.number.clone(),
+            account_name: self.name.clone(),
+            kind: kind.to_string(),
+            currency: currency.to_string(),
+            amount,
+            rate,
+            converted_amount: if currency == "PHP" {
+                amount
+            } else {
+                currency::convert(amount, &currency, &"PHP", rates)
+            },
+            charges,
+            balance: self.balance,
+        }
+    }
+
+    /// Checks whether balance can be deposited to the account.
+    fn check_deposit(&self) -> Result<(), String> {
+        match self.kind {
+            AccountType::TimeDeposit { .. } => {
+                Err(String::from("Time deposits cannot be topped up after their placement!"))
+            }
+            AccountType::Loan(_) => Err(String::from(
+                "Loans can only be paid through their installments or payoff!",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds deposited balance, in the account's currency, to the account, returning the deposit's reference number.
+    fn post_deposit(&mut self, kind: TransactionKind, amount: f64) -> String {
+        self.balance += amount;
+
+        self.record_transaction(kind, amount)
+    }
+
+    /// Deposits an amount given in some currency on a date, booking the currency and placing the deposit on hold if it
+    /// needs to clear.
+    ///
+    /// Returns the transaction's reference number, the amount in PHP and the hold placed, if any.
+    fn deposit_in(
+        &mut self,
+        currency: &str,
+        input_amount: f64,
+        rates: &HashMap<&str, f64>,
+        is_cheque: bool,
+        date: NaiveDate,
+    ) -> (String, f64, Option<Hold>) {
+        let amount = if currency == "PHP" {
+            input_amount
+        } else {
+            currency::convert(input_amount, &currency, &"PHP", rates)
+        };
+        let reference = self.post_deposit(TransactionKind::Deposit, amount);
+
+        fx_position::book(currency, input_amount, amount);
+
+        let hold = Hold::for_deposit(amount, currency, is_cheque, date);
+
+        if let Some(hold) = hold {
+            self.holds.push(hold);
+        }
+
+        (reference, amount, hold)
+    }
+
+    /// Deposits balance to the user's account.
+    ///
+    /// The user is prompted to input the currency and amount of balance to deposit, and whether it is deposited by
+    /// cheque. Cheques and large foreign currency deposits are held until they clear.
+    fn deposit_balance(&mut self, rates: &HashMap<&str, f64>) {
+        println!("Current Balance: {}", self.balance);
+
+        if let Err(reason) = self.check_deposit() {
+            println!("{reason}");
+
+            return;
+        }
+
+        let Some(currency) = currency::prompt_currency("Currency: ") else {
+            return;
+        };
+
+        let Some(is_cheque) = confirm("Deposit by cheque? (Y/N): ") else {
+            return;
+        };
+
+        println!();
+
+        let Some(input_amount) = prompt_number(
+            "Deposit Amount: ",
+            f64::MIN_POSITIVE..=f64::MAX,
+            "Deposit amount must be a positive floating point number!",
+        ) else {
+            return;
+        };
+        let (reference, _, hold) = self.deposit_in(currency, input_amount, rates, is_cheque, clock::today());
+
+        println!("Updated Balance: {}", self.balance);
+
+        if let Some(hold) = hold {
+            println!("On Hold: {} (clears on {})", hold.amount, hold.release_date);
+            println!("Available Balance: {}", self.available_balance());
+        }
+
+        self.receipt(reference, TransactionKind::Deposit, currency, input_amount, rates, 0.0)
+            .issue();
+    }
+
+    /// Gets the balance that can be spent, which excludes the deposits that have yet to clear.
+    fn available_balance(&self) -> f64 {
+        self.balance - self.holds.iter().map(|h| h.amount).sum::<f64>()
+    }
+
+    /// Releases the holds on the deposits that have cleared by a date.
+    fn release_holds(&mut self, date: NaiveDate) {
+        let (released, held) = self.holds.iter().partition::<Vec<Hold>, _>(|h| h.release_date <= date);
+
+        self.holds = held;
+
+        for hold in released {
+            notice(&format!("[{date}] {}: Released hold of {}", self.number, hold.amount));
+        }
+    }
+
+    /// Gets the limits on the withdrawals from the account, as configured for its type.
+    fn withdrawal_limits(&self, limit_table: &WithdrawalLimitTable) -> WithdrawalLimits {
+        limit_table
+            .get(self.kind.title())
+            .copied()
+            .unwrap_or(WithdrawalLimits::NONE)
+    }
+
+    /// Gets the withdrawals (including outgoing transfers) made from the account on dates matching a predicate.
+    fn withdrawals_on(&self, is_on: impl Fn(NaiveDate) -> bool) -> impl Iterator<Item = &Transaction> {
+        self.transactions
+            .iter()
+            .filter(|t| t.kind == TransactionKind::Withdrawal || t.kind == TransactionKind::TransferOut)
+            .filter(move |t| is_on(t.timestamp.date_naive()))
+    }
+
+    /// Counts the withdrawals made from the account in the same period as a date.
+    fn withdrawal_cnt(&self, date: NaiveDate, period: LimitPeriod) -> usize {
+        match period {
+            LimitPeriod::Daily => self.withdrawals_on(|d| d == date).count(),
+            LimitPeriod::Monthly => self
+                .withdrawals_on(|d| d.year() == date.year() && d.month() == date.month())
+                .count(),
+        }
+    }
+
+    /// Totals the amounts withdrawn from the account on a date.
+    fn daily_withdrawal_total(&self, date: NaiveDate) -> f64 {
+        self.withdrawals_on(|d| d == date).map(|t| t.amount).sum()
+    }
+
+    /// Checks whether the account can be withdrawn from on a date, regardless of the amount.
+    fn check_withdrawal_allowed(&self, date: NaiveDate, limits: &WithdrawalLimits) -> Result<(), String> {
+        if let AccountType::Loan(_) = self.kind {
+            return Err(String::from("Loans cannot be withdrawn from!"));
+        }
+
+        if let Some(max_cnt) = limits.max_cnt
+            && self.withdrawal_cnt(date, limits.cnt_period) >= max_cnt
+        {
+            return Err(format!(
+                "{} accounts only allow {max_cnt} withdrawals per {}!",
+                self.kind,
+                match limits.cnt_period {
+                    LimitPeriod::Daily => "day",
+                    LimitPeriod::Monthly => "month",
+                }
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether an amount, in PHP, is within the single and daily withdrawal limits of the account on a date.
+    fn check_withdrawal_limits(&self, amount: f64, date: NaiveDate, limits: &WithdrawalLimits) -> Result<(), String> {
+        if let Some(max_single_amount) = limits.max_single_amount
+            && amount > max_single_amount
+        {
+            return Err(format!(
+                "Withdraw amount must not exceed the single withdrawal limit of {max_single_amount} PHP!"
+            ));
+        }
+
+        if let Some(max_daily_amount) = limits.max_daily_amount {
+            let allowance = (max_daily_amount - self.daily_withdrawal_total(date)).max(0.0);
+
+            if amount > allowance {
+                return Err(format!(
+                    "Withdraw amount must not exceed the remaining daily allowance of {allowance} PHP!"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints how much more can be withdrawn from the account on a date.
+    fn print_withdrawal_allowance(&self, date: NaiveDate, limits: &WithdrawalLimits) {
+        if let Some(max_daily_amount) = limits.max_daily_amount {
+            println!(
+                "Remaining Daily Allowance: {}",
+                (max_daily_amount - self.daily_withdrawal_total(date)).max(0.0)
+            );
+        }
+
+        if let Some(max_cnt) = limits.max_cnt {
+            println!(
+                "Withdrawals Left {}: {}",
+                match limits.cnt_period {
+                    LimitPeriod::Daily => "Today",
+                    LimitPeriod::Monthly => "This Month",
+                },
+                max_cnt.saturating_sub(self.withdrawal_cnt(date, limits.cnt_period))
+            );
+        }
+    }
+
+    /// Computes the charges of withdrawing an amount, in the account's currency, on a date.
+    ///
+    /// The withdrawal is refused if the amount and its charges exceed the available balance plus the overdraft limit.
+    fn withdrawal_charges(&self, amount: f64, date: NaiveDate) -> Result<WithdrawalCharges, String> {
+        let available_balance = self.available_balance();
+        let penalty = match self.kind {
+            AccountType::TimeDeposit { maturity_date, .. } if date < maturity_date => {
+                (amount * AccountType::TIME_DEPOSIT_EARLY_WITHDRAWAL_PENALTY_RATE * 100.0).round() / 100.0
+            }
+            _ => 0.0,
+        };
+        let overdraft_fee = if self.overdraft_limit > 0.0 && available_balance - amount < 0.0 {
+            AccountType::OVERDRAFT_FEE
+        } else {
+            0.0
+        };
+
+        if available_balance - amount - penalty - overdraft_fee < -self.overdraft_limit {
+            return Err(String::from(if self.overdraft_limit > 0.0 {
+                "Withdraw amount and overdraft fee must not exceed the available balance plus the overdraft limit!"
+            } else {
+                "Withdraw amount must be less than the available balance!"
+            }));
+        }
+
+        Ok(WithdrawalCharges { penalty, overdraft_fee })
+    }
+
+    /// Takes withdrawn balance, in the account's currency, and its charges from the account, returning the withdrawal's
+    /// reference number.
+    fn post_withdrawal(&mut self, kind: TransactionKind, amount: f64, charges: &WithdrawalCharges) -> String {
+        self.balance -= amount;
+
+        let reference = self.record_transaction(kind, amount);
+
+        if charges.penalty > 0.0 {
+            self.balance -= charges.penalty;
+
+            self.record_transaction(TransactionKind::Penalty, charges.penalty);
+        }
+
+        if charges.overdraft_fee > 0.0 {
+            self.balance -= charges.overdraft_fee;
+
+            self.record_transaction(TransactionKind::OverdraftFee, charges.overdraft_fee);
+        }
+
+        reference
+    }
+
+    /// Withdraws balance from the user's account.
+    ///
+    /// The user is prompted to input the currency and amount of balance to withdraw. If the amount (and any fees) is
+    /// greater than the account's available balance plus its overdraft limit, the transaction is cancelled. Withdrawals that
+    /// overdraw the account are charged a fee. Withdrawals are limited in their amount, daily total and number per period
+    /// as configured for the account's type, and withdrawing from a time deposit before it matures is penalized.
+    fn withdraw_balance(&mut self, rates: &HashMap<&str, f64>, limit_table: &WithdrawalLimitTable) {
+        println!("Current Balance: {}", self.balance);
+
+        if !self.holds.is_empty() {
+            println!("Available Balance: {}", self.available_balance());
+        }
+
+        let today = clock::today();
+        let limits = self.withdrawal_limits(limit_table);
+
+        self.print_withdrawal_allowance(today, &limits);
+
+        if let Err(reason) = self.check_withdrawal_allowed(today, &limits) {
+            println!("{reason}");
+
+            return;
+        }
+
+        let Some(currency) = currency::prompt_currency("Currency: ") else {
+            return;
+        };
+
+        println!();
+
+        let Some((input_amount, amount, charges)) = prompt_valid("Withdraw Amount: ", |input| {
+            let input_amount = match input.parse::<f64>() {
+                Ok(amount) if (f64::MIN_POSITIVE..=f64::MAX).contains(&amount) => amount,
+                _ => {
+                    return Err(String::from(
+                        "Withdraw amount must be a positive floating point number!",
+                    ));
+                }
+            };
+            let amount = if currency == "PHP" {
+                input_amount
+            } else {
+                currency::convert(input_amount, &currency, &"PHP", rates)
+            };
+
+            self.check_withdrawal_limits(amount, today, &limits)?;
+
+            Ok((input_amount, amount, self.withdrawal_charges(amount, today)?))
+        }) else {
+            return;
+        };
+        if let AccountType::TimeDeposit { maturity_date, .. } = self.kind
+            && charges.penalty > 0.0
+        {
+            println!(
+                "Early Withdrawal Penalty: {} (matures on {maturity_date})",
+                charges.penalty
+            );
+
+            println!();
+
+            if confirm("Withdraw before maturity anyway? (Y/N): ") != Some(true) {
+                println!("The withdrawal was cancelled.");
+
+                return;
+            }
+        }
+
+        let reference = self.post_withdrawal(TransactionKind::Withdrawal, amount, &charges);
+
+        fx_position::book(currency, -input_amount, amount);
+
+        if charges.overdraft_fee > 0.0 {
+            println!("Overdraft Fee: {}", charges.overdraft_fee);
+        }
+
+        println!("Updated Balance: {}", self.balance);
+
+        if !self.holds.is_empty() {
+            println!("Available Balance: {}", self.available_balance());
+        }
+
+        if self.overdraft_limit > 0.0 {
+            println!(
+                "Available Overdraft: {}",
+                self.overdraft_limit + self.available_balance().min(0.0)
+            );
+        }
+
+        self.print_withdrawal_allowance(today, &limits);
+
+        self.receipt(
+            reference,
+            TransactionKind::Withdrawal,
+            currency,
+            input_amount,
+            rates,
+            charges.penalty + charges.overdraft_fee,
+        )
+        .issue();
+    }
+
+    /// Calculates and prints the daily increase to the account's balance from interest.
+    ///
+    /// The user is prompted to input the number of days to calculate for. Overdrawn balances are instead projected to
+    /// decrease from the overdraft interest charged on them, and loans instead print their amortization schedule.
+    fn calculate_interest(&self) {
+        if let AccountType::Loan(loan) = self.kind {
+            self.print_amortization_schedule(&loan);
+
+            return;
+        }
+
+        let balance = self.balance;
+        let annual_interest_rate = self.current_annual_interest_rate();
+
+        println!("Current Balance: {balance}");
+        println!("Currency: {}", self.currency);
+
+        if balance < 0.0 {
+            println!("Overdraft Interest Rate: {}%", (annual_interest_rate * 100.0) as i32);
+        } else {
+            println!("Interest Rate: {}%", (annual_interest_rate * 100.0) as i32);
+        }
+
+        println!();
+
+        if annual_interest_rate == 0.0 {
+            println!("{} accounts do not earn interest!", self.kind);
+
+            return;
+        }
+
+        let Some(day_cnt) = prompt_number(
+            "Total Number of Days: ",
+            0..=Account::MAX_INTEREST_PROJECTION_DAYS,
+            &format!(
+                "Number must be a whole number (integer) from 0 to {}!",
+                Account::MAX_INTEREST_PROJECTION_DAYS
+            ),
+        ) else {
+            return;
+        };
+
+        println!();
+
+        println!("Day | Interest | Balance |");
+
+        let (daily_interest, balances) = self.project_interest(day_cnt);
+
+        for (i, balance) in balances.into_iter().enumerate() {
+            println!(
+                "{day:<3} | {interest:<8} | {balance:<7.2} |",
+                day = i + 1,
+                interest = daily_interest,
+                balance = balance
+            );
+        }
+    }
+
+    /// Gets the annual interest rate that the balance earns, or is charged when the account is overdrawn.
+    fn current_annual_interest_rate(&self) -> f64 {
+        if self.balance < 0.0 {
+            config::get().interest.overdraft_annual_rate
+        } else {
+            self.kind.annual_interest_rate()
+        }
+    }
+
+    /// Projects the balance over a number of days, returning the daily interest and the balance at the end of each day.
+    fn project_interest(&self, day_cnt: u32) -> (f64, Vec<f64>) {
+        let daily_interest = (self.balance * (self.current_annual_interest_rate() / 365.0) * 100.0).round() / 100.0;
+        let balances = (0..day_cnt)
+            .scan(self.balance, |balance, _| {
+                *balance += daily_interest;
+
+                Some(*balance)
+            })
+            .collect();
+
+        (daily_interest, balances)
+    }
+
+    /// Prints every installment of a loan along with whether it was paid.
+    fn print_amortization_schedule(&self, loan: &Loan) {
+        let today = clock::today();
+
+        println!("Outstanding Balance: {}", -self.balance);
+        println!("Currency: {}", self.currency);
+        println!("Interest Rate: {}%", loan.annual_interest_rate * 100.0);
+        println!(
+            "Repayment Method: {}",
+            loan::REPAYMENT_METHOD_TITLES[loan.method as usize]
+        );
+
+        println!();
+
+        println!("Month | Due Date   | Payment    | Principal  | Interest   | Balance    | Status  |");
+
+        for installment in loan.amortization_schedule() {
+            println!(
+                "{month:<5} | {due_date} | {payment:<10.2} | {principal:<10.2} | {interest:<10.2} | {balance:<10.2} | {status:<7} |",
+                month = installment.number,
+                due_date = installment.due_date,
+                payment = installment.payment,
+                principal = installment.principal,
+                interest = installment.interest,
+                balance = installment.balance,
+                status = if installment.number <= loan.paid_installment_cnt {
+                    "Paid"
+                } else if Loan::is_overdue(&installment, today) {
+                    "Overdue"
+                } else {
+                    "Pending"
+                }
+            );
+        }
+    }
+
+    /// Posts the payment of a loan's next installment.
+    ///
+    /// The user is prompted to confirm the payment, which includes a penalty if it is made after the installment's grace
+    /// period.
+    fn pay_loan_installment(&mut self) {
+        let AccountType::Loan(mut loan) = self.kind else {
+            println!("Only loan accounts have installments!");
+
+            return;
+        };
+
+        println!("Outstanding Balance: {}", -self.balance);
+
+        let Some(installment) = loan.next_installment() else {
+            println!("The loan has been paid off!");
+
+            return;
+        };
+        let penalty = Loan::late_penalty(&installment, clock::today());
+
+        println!("Installment: {} of {}", installment.number, loan.term_months);
+        println!("Due Date: {}", installment.due_date);
+        println!("Amount Due: {}", installment.payment);
+
+        if penalty > 0.0 {
+            println!("Late Payment Penalty: {penalty}");
+        }
+
+        println!();
+
+        if confirm("Post this payment? (Y/N): ") != Some(true) {
+            println!("The payment was not posted.");
+
+            return;
+        }
+
+        self.balance -= installment.interest;
+
+        self.record_transaction(TransactionKind::LoanInterest, installment.interest);
+
+        if penalty > 0.0 {
+            self.balance -= penalty;
+
+            self.record_transaction(TransactionKind::LatePaymentPenalty, penalty);
+        }
+
+        self.balance = -installment.balance;
+
+        self.record_transaction(TransactionKind::LoanPayment, installment.payment + penalty);
+
+        loan.paid_installment_cnt += 1;
+        self.kind = AccountType::Loan(loan);
+
+        println!("Outstanding Balance: {}", -self.balance);
+    }
+
+    /// Prints the amount needed to pay off a loan today.
+    ///
+    /// The user is prompted to choose whether to pay off the loan at the quoted amount.
+    fn quote_loan_payoff(&mut self) {
+        let AccountType::Loan(mut loan) = self.kind else {
+            println!("Only loan accounts can be paid off!");
+
+            return;
+        };
+
+        if loan.next_installment().is_none() {
+            println!("The loan has been paid off!");
+
+            return;
+        }
+
+        let today = clock::today();
+        let quote = loan.payoff_quote(today);
+
+        println!("Quote Date: {today}");
+        println!("Principal: {}", quote.principal);
+        println!("Accrued Interest: {}", quote.interest);
+        println!("Late Payment Penalties: {}", quote.penalties);
+        println!("Payoff Amount: {:.2}", quote.total());
+
+        println!();
+
+        if confirm("Pay off the loan now? (Y/N): ") != Some(true) {
+            return;
+        }
+
+        self.balance -= quote.interest;
+
+        self.record_transaction(TransactionKind::LoanInterest, quote.interest);
+
+        if quote.penalties > 0.0 {
+            self.balance -= quote.penalties;
+
+            self.record_transaction(TransactionKind::LatePaymentPenalty, quote.penalties);
+        }
+
+        self.balance = 0.0;
+
+        self.record_transaction(TransactionKind::LoanPayment, quote.total());
+
+        loan.paid_installment_cnt = loan.term_months;
+        self.kind = AccountType::Loan(loan);
+
+        println!("The loan was paid off.");
+    }
+
+    /// Runs the account's processing at the start of a day.
+    ///
+    /// Cleared deposits are released from hold, the previous day's interest is charged on an overdrawn balance or
+    /// accrued on a savings balance, accrued savings interest is credited at the start of each month, and matured time
+    /// deposits roll over.
+    fn start_day(&mut self, date: NaiveDate) {
+        if self.is_closed {
+            return;
+        }
+
+        self.release_holds(date);
+
+        if self.balance < 0.0 && self.overdraft_limit > 0.0 {
+            let interest =
+                (-self.balance * (config::get().interest.overdraft_annual_rate / 365.0) * 100.0).round() / 100.0;
+
+            self.balance -= interest;
+
+            self.record_transaction(TransactionKind::OverdraftInterest, interest);
+        }
+
+        if self.kind == AccountType::Savings {
+            if self.balance > 0.0 {
+                self.accrued_interest += self.balance * (config::get().interest.savings_annual_rate / 365.0);
+            }
+
+            let interest = (self.accrued_interest * 100.0).round() / 100.0;
+
+            if date.day() == 1 && interest > 0.0 {
+                self.accrued_interest = 0.0;
+                self.balance += interest;
+
+                self.record_transaction(TransactionKind::Interest, interest);
+
+                notice(&format!("[{date}] {}: Credited {interest} interest", self.number));
+            }
+        }
+
+        self.roll_over_time_deposit(date);
+    }
+
+    /// Changes how far below zero the balance of a checking account can go.
+    ///
+    /// The user is prompted to input the new limit, which must cover the current overdrawn balance.
+    fn set_overdraft_limit(&mut self) {
+        println!("Current Balance: {}", self.balance);
+        println!("Overdraft Limit: {}", self.overdraft_limit);
+
+        if self.kind != AccountType::Checking {
+            println!("Only checking accounts can be overdrawn!");
+
+            return;
+        }
+
+        println!();
+
+        let Some(limit) = prompt_valid("New Overdraft Limit: ", |input| match input.parse::<f64>() {
+            Ok(limit) if limit < -self.balance => Err(String::from(
+                "Overdraft limit must cover the current overdrawn balance!",
+            )),
+            Ok(limit) if (0.0..=f64::MAX).contains(&limit) => Ok(limit),
+            _ => Err(String::from(
+                "Overdraft limit must be a non-negative floating point number!",
+            )),
+        }) else {
+            return;
+        };
+
+        self.overdraft_limit = limit;
+
+        println!("Updated Overdraft Limit: {}", self.overdraft_limit);
+    }
+
+    /// Rolls a matured time deposit over into new terms, crediting the interest earned over each finished term.
+    fn roll_over_time_deposit(&mut self, today: NaiveDate) {
+        let AccountType::TimeDeposit {
+            term_days,
+            mut maturity_date,
+        } = self.kind
+        else {
+            return;
+        };
+
+        while maturity_date <= today {
+            let interest = (self.balance * config::get().interest.time_deposit_annual_rate * term_days as f64 / 365.0
+                * 100.0)
+                .round()
+                / 100.0;
+
+            self.balance += interest;
+
+            self.record_transaction(TransactionKind::Interest, interest);
+
+            maturity_date += TimeDelta::days(term_days);
+
+            notice(&format!(
+                "[{today}] {}: Matured with {interest} interest and rolled over until {maturity_date}",
+                self.number
+            ));
+        }
+
+        self.kind = AccountType::TimeDeposit {
+            term_days,
+            maturity_date,
+        };
+    }
+
+    /// Closes the account so that it can no longer be used in transactions.
+    ///
+    /// The user is prompted to confirm the closure. Any remaining balance must be paid out before the account is closed,
+    /// and overdrawn balances must be settled first.
+    fn close(&mut self) {
+        println!("Current Balance: {}", self.balance);
+        println!("Currency: {}", self.currency);
+
+        println!();
+
+        if self.balance < 0.0 {
+            if let AccountType::Loan(_) = self.kind {
+                println!("Loans must be paid off before they are closed!");
+            } else {
+                println!("Overdrawn accounts must be settled before they are closed!");
+            }
+
+            return;
+        }
+
+        if !self.holds.is_empty() {
+            println!("Accounts with deposits on hold cannot be closed until the deposits clear!");
+
+            return;
+        }
+
+        let penalty = match self.kind {
+            AccountType::TimeDeposit { maturity_date, .. } if clock::today() < maturity_date => {
+                (self.balance * AccountType::TIME_DEPOSIT_EARLY_WITHDRAWAL_PENALTY_RATE * 100.0).round() / 100.0
+            }
+            _ => 0.0,
+        };
+
+        if penalty > 0.0 {
+            println!("Early Withdrawal Penalty: {penalty}");
+
+            println!();
+        }
+
+        let msg = if self.balance > 0.0 {
+            "Pay out the remaining balance and close the account? (Y/N): "
+        } else {
+            "Close the account? (Y/N): "
+        };
+
+        if confirm(msg) != Some(true) {
+            println!("The account was not closed.");
+
+            return;
+        }
+
+        if penalty > 0.0 {
+            self.balance -= penalty;
+
+            self.record_transaction(TransactionKind::Penalty, penalty);
+        }
+
+        if self.balance > 0.0 {
+            let payout = self.balance;
+
+            self.balance = 0.0;
+
+            self.record_transaction(TransactionKind::Payout, payout);
+
+            println!("Paid Out: {payout}");
+        }
+
+        self.is_closed = true;
+        self.standing_orders.clear();
+
+        println!("The account was closed.");
+    }
+}
+
+/// Checks whether an account name resembles a searched name.
+///
+/// Names resemble each other if, ignoring case, one contains the other or they are at most two edits apart.
+fn is_similar_name(query: &str, name: &str) -> bool {
+    let query = query.to_lowercase().chars().collect::<Vec<char>>();
+    let name = name.to_lowercase().chars().collect::<Vec<char>>();
 
-        input.trim().to_string()
+    if query.is_empty() || name.is_empty() {
+        return false;
     }
-}
 
-mod currency {
-    use crate::io_util::{print_ordered_list, prompt};
-    use std::collections::HashMap;
+    if name.windows(query.len()).any(|w| w == query) || query.windows(name.len()).any(|w| w == name) {
+        return true;
+    }
 
-    /// The number of exchangeable currencies.
-    pub const CURRENCY_COUNT: usize = 6;
-    /// The titles or labels of the exchangeable currencies.
-    pub const CURRENCIES_TITLES: [&str; CURRENCY_COUNT] = [
-        "Philippine Peso (PHP)",
-        "United States Dollar (USD)",
-        "Japanese Yen (JPY)",
-        "British Pound Sterling (GBP)",
-        "Euro (EUR)",
-        "Chinese Yuan Renminni (CNY)",
-    ];
-    /// The [ISO 4217](https://en.wikipedia.org/wiki/ISO_4217) codes of the exchangeable currencies.
-    pub const CURRENCIES_CODES: [&str; CURRENCY_COUNT] = ["PHP", "USD", "JPY", "GBP", "EUR", "CNY"];
+    let mut prev_distances = (0..=name.len()).collect::<Vec<usize>>();
 
-    /// Converts an amount from one currency to another.
-    pub fn convert(amount: f64, src: &&str, dest: &&str, rates: &HashMap<&str, f64>) -> f64 {
-        let src_php_amount = if *src == "PHP" { amount } else { amount * rates[src] };
+    for (i, q) in query.iter().enumerate() {
+        let mut distances = vec![i + 1];
 
-        if *dest == "PHP" {
-            src_php_amount
-        } else {
-            src_php_amount * rates[dest]
+        for (j, n) in name.iter().enumerate() {
+            distances.push(
+                (prev_distances[j] + usize::from(q != n))
+                    .min(prev_distances[j + 1] + 1)
+                    .min(distances[j] + 1),
+            );
         }
+
+        prev_distances = distances;
     }
 
-    /// Calculates and prints how much one currency is worth in another.
-    ///
-    /// The user is prompted to input the amount and what currencies to exchange.
-    pub fn exchange(rates: &HashMap<&str, f64>) {
-        println!("Source Currency Options:");
-        print_ordered_list(&CURRENCIES_TITLES);
+    prev_distances[name.len()] <= 2
+}
 
-        println!();
+/// Prompts the user to input an account number and finds the index of the open account with that number.
+///
+/// Account numbers are checked against their check digit. If a name is inputted instead, the open accounts with that or
+/// a similar name are listed for the user to choose from.
+fn prompt_account_idx(accounts: &[Account]) -> Option<usize> {
+    let mut candidates = Vec::<&str>::new();
 
-        let src_idx = match prompt("Source Currency: ").parse::<usize>() {
-            Ok(idx) => idx - 1,
-            Err(_) => {
-                println!("ID must be a positive whole number (integer)!");
+    for account in accounts.iter().filter(|a| !a.is_closed) {
+        if !candidates.contains(&account.name.as_str()) {
+            candidates.push(&account.name);
+        }
 
-                return;
+        candidates.push(&account.number);
+    }
+
+    // The accounts with the inputted name, or else those with a similar name, if no number was inputted.
+    let (mut matching_idxs, is_similar) = prompt_valid_completing("Account Number or Name: ", &candidates, |query| {
+        if !query.is_empty() && query.chars().all(|c| c.is_ascii_digit()) {
+            if !account_number::is_valid(query) {
+                return Err(String::from("Account number is invalid, check for mistyped digits!"));
             }
-        };
 
-        if src_idx >= CURRENCY_COUNT {
-            println!("No currency with this ID exists!");
+            return match accounts.iter().position(|a| !a.is_closed && a.number == query) {
+                Some(idx) => Ok((vec![idx], false)),
+                None => Err(String::from("No account with this number exists!")),
+            };
+        }
+
+        let matching_idxs = accounts
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| !a.is_closed && a.name == query)
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
 
-            return;
+        if !matching_idxs.is_empty() {
+            return Ok((matching_idxs, false));
         }
 
-        let src_amount = match prompt("Source Amount: ").parse::<f64>() {
-            Ok(amount) => amount,
-            Err(_) => {
-                println!("Amount must be a floating point number!");
+        let similar_idxs = accounts
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| !a.is_closed && is_similar_name(query, &a.name))
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
 
-                return;
-            }
-        };
+        if similar_idxs.is_empty() {
+            return Err(String::from("No account with this name exists!"));
+        }
 
-        println!();
+        Ok((similar_idxs, true))
+    })?;
 
-        println!("Exchanged Currency Options:");
-        print_ordered_list(&CURRENCIES_TITLES);
+    if matching_idxs.len() == 1 && !is_similar {
+        return matching_idxs.pop();
+    }
+
+    if is_similar {
+        println!("No account with this name exists!");
 
         println!();
 
-        let exchange_idx = match prompt("Exchange Currency: ").parse::<usize>() {
-            Ok(idx) => idx - 1,
-            Err(_) => {
-                println!("ID must be a positive whole number (integer)!");
+        println!("Similar Accounts:");
+    } else {
+        println!("Accounts With This Name:");
+    }
 
-                return;
-            }
-        };
+    print_ordered_list(
+        &matching_idxs
+            .iter()
+            .map(|&i| format!("{} ({})", accounts[i].name, accounts[i].number))
+            .collect::<Vec<String>>(),
+    );
 
-        if exchange_idx >= CURRENCY_COUNT {
-            println!("No currency with this ID exists!");
+    println!();
 
-            return;
+    let idx = prompt_valid("Select Account (Leave Blank to Cancel): ", |input| match input {
+        "" => Ok(None),
+        input => match input.parse::<usize>() {
+            Ok(id) if (1..=matching_idxs.len()).contains(&id) => Ok(Some(matching_idxs[id - 1])),
+            _ => Err(String::from("No account with this ID exists!")),
+        },
+    })??;
+
+    println!();
+
+    println!("Account Number: {}", accounts[idx].number);
+
+    Some(idx)
+}
+
+/// Prompts the user to input a new passphrase for the data and journal files, which must be inputted twice.
+fn prompt_new_passphrase() -> Option<String> {
+    let passphrase = prompt_secret("New Passphrase: ", |input| {
+        if input.chars().count() < MIN_PASSPHRASE_LEN {
+            return Err(format!(
+                "Passphrase must be at least {MIN_PASSPHRASE_LEN} characters long!"
+            ));
         }
 
+        Ok(input.to_string())
+    })?;
+
+    prompt_secret("Confirm Passphrase: ", |input| {
+        if input != passphrase {
+            return Err(String::from("Passphrases do not match!"));
+        }
+
+        Ok(input.to_string())
+    })
+}
+
+/// Changes the passphrase that the data and journal files are encrypted with.
+///
+/// The user is prompted to input the current passphrase before the new one.
+fn change_passphrase(data: &storage::BankDataRef) {
+    if !storage::is_unlocked() {
         println!(
-            "Exchange Amount: {}",
-            convert(
-                src_amount,
-                &CURRENCIES_CODES[src_idx],
-                &CURRENCIES_CODES[exchange_idx],
-                rates
-            )
+            "The data and journal files are not encrypted, set files.encrypt in the configuration to encrypt them!"
         );
+
+        return;
     }
 
-    /// Updates the exchange rate between a currency and Philippine Pesos.
-    ///
-    /// The user is prompted to input the currency and its value in PHP.
-    pub fn set_exchange_rates(rates: &mut HashMap<&str, f64>) {
-        print_ordered_list(&CURRENCIES_TITLES[1..]);
+    if prompt_secret("Current Passphrase: ", |passphrase| {
+        storage::check_passphrase(passphrase).map_err(|err| err.to_string())
+    })
+    .is_none()
+    {
+        return;
+    }
 
-        println!();
+    println!();
 
-        let idx = match prompt("Select Foreign Currency: ").parse::<usize>() {
-            Ok(idx) => idx,
-            Err(_) => {
-                println!("ID must be a positive whole number (integer)!");
+    let Some(passphrase) = prompt_new_passphrase() else {
+        return;
+    };
 
-                return;
-            }
-        };
+    println!();
 
-        if idx >= CURRENCY_COUNT {
-            println!("No currency with this ID exists!");
+    match storage::change_passphrase(&passphrase, data) {
+        Ok(()) => println!("Re-encrypted the data and journal files with the new passphrase!"),
+        Err(err) => println!("Failed to change the passphrase: {err}"),
+    }
+}
 
-            return;
-        }
+/// Prints every registered account along with its balance.
+///
+/// The user is prompted to choose whether the accounts are sorted by name or by balance (highest first).
+fn list_accounts(accounts: &[Account]) {
+    if accounts.is_empty() {
+        println!("No accounts have been registered!");
 
-        let rate = match prompt("Exchange Rate: ").parse::<f64>() {
-            Ok(rate) => rate,
-            Err(_) => {
-                println!("Amount must be a floating point number!");
+        return;
+    }
 
-                return;
-            }
-        };
+    let Some(is_sorted_by_name) = prompt_valid("Sort By ([N]ame/[B]alance): ", |input| {
+        match input.to_uppercase().as_str() {
+            "N" => Ok(true),
+            "B" => Ok(false),
+            _ => Err(String::from("Only accepting a [N]ame or [B]alance answer!")),
+        }
+    }) else {
+        return;
+    };
+    let mut sorted_accounts = accounts.iter().collect::<Vec<&Account>>();
 
-        rates.insert(CURRENCIES_CODES[idx], rate);
+    if is_sorted_by_name {
+        sorted_accounts.sort_by_key(|a| a.name.to_lowercase());
+    } else {
+        sorted_accounts.sort_by(|a, b| b.balance.total_cmp(&a.balance));
     }
-}
 
-use io_util::{print_ordered_list, prompt};
-use std::collections::HashMap;
+    let name_width = sorted_accounts
+        .iter()
+        .map(|a| a.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
 
-/// The titles of the available transactional procedures.
-const TRANSACTION_TITLES: [&str; 6] = [
-    "Register Account Name",
-    "Deposit Amount",
-    "Withdraw Amount",
-    "Currency Exchange",
-    "Record Exchange Rates",
-    "Show Interest Amount",
-];
+    println!();
 
-/// A simple user bank account.
-#[derive(PartialEq)]
-struct Account {
-    /// The name of the owner of the account.
-    name: String,
-    /// The current balance of the account.
-    balance: f64,
-    /// The currency that the account's balance is based on.
-    currency: String,
+    println!(
+        "Number     | {:<name_width$} | Type         | Balance      | Currency | Status |",
+        "Name"
+    );
+
+    for account in sorted_accounts {
+        println!(
+            "{number:<10} | {name:<name_width$} | {kind:<12} | {balance:<12.2} | {currency:<8} | {status:<6} |",
+            number = account.number,
+            name = account.name,
+            kind = account.kind,
+            balance = account.balance,
+            currency = account.currency,
+            status = if account.is_closed { "Closed" } else { "Open" }
+        );
+    }
 }
-impl Account {
-    /// The fixed annual interest rate percentage.
-    const ANNUAL_INTEREST_RATE: f64 = 0.05;
 
-    /// Creates a new account with the default values.
-    fn new(name: String) -> Account {
-        Account {
-            name,
-            balance: 0.0,
-            currency: String::from("PHP"),
+/// Runs the bank's processing for every day after the last processed date up to a date.
+///
+/// Each day starts with the processing of every account, followed by the execution of the due scheduled transactions.
+fn process_days(
+    accounts: &mut [Account],
+    rates: &HashMap<&str, f64>,
+    limit_table: &WithdrawalLimitTable,
+    processed_date: &mut NaiveDate,
+    today: NaiveDate,
+) {
+    while *processed_date < today {
+        *processed_date += TimeDelta::days(1);
+
+        for account in accounts.iter_mut() {
+            account.start_day(*processed_date);
         }
+
+        standing_order::execute_due(accounts, rates, limit_table, *processed_date);
     }
+}
 
-    /// Deposits balance to the user's account.
-    ///
-    /// The user is prompted to input the currency and amount of balance to deposit.
-    fn deposit_balance(&mut self, rates: &HashMap<&str, f64>) {
-        println!("Current Balance: {}", self.balance);
+/// Gets the value following a command-line flag, if the flag was passed.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<String>>();
 
-        let currency = prompt("Currency: ").to_uppercase();
+    if let Some(path) = flag_value(&args, "--mock-rate-server") {
+        let port = flag_value(&args, "--port")
+            .and_then(|p| p.parse::<u16>().ok())
+            .unwrap_or(8080);
 
-        if !currency::CURRENCIES_CODES.iter().any(|c| *c == currency) {
-            println!("No currency with this code exists!");
+        match rate_provider::MockRateServer::bind(path, port).and_then(|server| Ok((server.url()?, server))) {
+            Ok((url, server)) => {
+                println!("Serving {path} at {url}");
 
-            return;
+                server.serve(None);
+            }
+            Err(err) => println!("Failed to serve the mock exchange rates: {err}"),
         }
 
-        println!();
+        return;
+    }
 
-        if let Ok(amount) = prompt("Deposit Amount: ").parse::<f64>() {
-            self.balance += if currency == "PHP" {
-                amount
-            } else {
-                currency::convert(amount, &currency.as_str(), &"PHP", rates)
-            };
+    if let Some(path) = flag_value(&args, "--config")
+        && let Err(errors) = config::load(path)
+    {
+        println!("Invalid configuration:");
 
-            println!("Updated Balance: {}", self.balance);
-        } else {
-            println!("Deposit amount must be a floating point number!");
+        for error in errors {
+            println!("  {error}");
         }
+
+        return;
     }
 
-    /// Withdraws balance from the user's account.
-    ///
-    /// The user is prompted to input the currency and amount of balance to withdraw. If the amount is greater than the
-    /// account's current balance, the transaction is cancelled.
-    fn withdraw_balance(&mut self, rates: &HashMap<&str, f64>) {
-        println!("Current Balance: {}", self.balance);
+    if let Some(date) = flag_value(&args, "--clock-start") {
+        match date.parse::<NaiveDate>() {
+            Ok(date) => clock::start_at(date),
+            Err(_) => {
+                println!("Clock start must be a date in the YYYY-MM-DD format!");
+
+                return;
+            }
+        }
+    }
 
-        let currency = prompt("Currency: ").to_uppercase();
+    if let Some(dir) = flag_value(&args, "--receipts-dir") {
+        receipt::save_to(dir.into());
+    } else if let Some(dir) = &config::get().files.receipts_dir {
+        receipt::save_to(dir.clone());
+    }
 
-        if !currency::CURRENCIES_CODES.iter().any(|c| *c == currency) {
-            println!("No currency with this code exists!");
+    let mut rate_feed = flag_value(&args, "--rate-source").map(|src| RateFeed::new(rate_provider::from_source(src)));
+    let mut accounts = Vec::<Account>::new();
+    let mut exchange_rates = HashMap::<&str, f64>::new();
+    let mut exchange_rate_dates = HashMap::<&str, NaiveDate>::new();
+    let mut processed_date = clock::today();
+    let mut withdrawal_limits = withdrawal_limit::default_table();
+    let mut quote_book = currency::QuoteBook::default();
 
-            return;
-        }
+    for code in currency::CURRENCIES_CODES.iter().skip(1) {
+        exchange_rates.insert(
+            code,
+            config::get().currency.initial_rates.get(*code).copied().unwrap_or(1.0),
+        );
+    }
 
-        println!();
+    let is_rpc = args.iter().any(|a| a == "--rpc");
 
-        if let Ok(mut amount) = prompt("Withdraw Amount: ").parse::<f64>() {
-            amount = if currency == "PHP" {
-                amount
-            } else {
-                currency::convert(amount, &currency.as_str(), &"PHP", rates)
-            };
+    if is_rpc {
+        io_util::send_notices_to_stderr();
+    }
 
-            if self.balance - amount < 0.0 {
-                println!("Withdraw amount must be less than the current balance!");
+    match storage::encryption() {
+        Ok(storage::Encryption::Disabled) => {}
+        Ok(_) if is_rpc => {
+            let Ok(passphrase) = env::var(PASSPHRASE_ENV_VAR) else {
+                eprintln!("The data and journal files are encrypted, set {PASSPHRASE_ENV_VAR} to their passphrase!");
 
                 return;
-            }
+            };
 
-            self.balance -= amount;
+            if let Err(err) = storage::unlock(&passphrase) {
+                eprintln!("Failed to unlock the data and journal files: {err}");
 
-            println!("Updated Balance: {}", self.balance);
-        } else {
-            println!("Withdraw amount must be a floating point number!");
+                return;
+            }
         }
-    }
+        Ok(storage::Encryption::New) => {
+            println!("The data and journal files will be encrypted with a new passphrase.");
 
-    /// Calculates and prints the daily increase to the account's balance from interest.
-    ///
-    /// The user is prompted to input the number of days to calculate for.
-    fn calculate_interest(&self) {
-        let &Account { mut balance, .. } = self;
+            let Some(passphrase) = prompt_new_passphrase() else {
+                return;
+            };
 
-        println!("Current Balance: {balance}");
-        println!("Currency: {}", self.currency);
-        println!("Interest Rate: {}%", (Account::ANNUAL_INTEREST_RATE * 100.0) as i32);
+            if let Err(err) = storage::unlock(&passphrase) {
+                println!("Failed to encrypt the data and journal files: {err}");
 
-        println!();
+                return;
+            }
+
+            println!();
+        }
+        Ok(storage::Encryption::Existing) => {
+            if prompt_secret("Passphrase: ", |passphrase| {
+                storage::unlock(passphrase).map_err(|err| err.to_string())
+            })
+            .is_none()
+            {
+                return;
+            }
 
-        if let Ok(day_cnt) = prompt("Total Number of Days: ").parse::<u32>() {
             println!();
+        }
+        Err(err) => {
+            println!("Failed to read the data file: {err}");
 
-            println!("Day | Interest | Balance |");
+            return;
+        }
+    }
 
-            let daily_interest = (balance * (Account::ANNUAL_INTEREST_RATE / 365.0) * 100.0).round() / 100.0;
+    match storage::load_data() {
+        Ok(Some(data)) => {
+            data.rates_into(&mut exchange_rates, &mut exchange_rate_dates);
 
-            for i in 1..=day_cnt {
-                balance += daily_interest;
+            accounts = data.accounts;
+            processed_date = data.processed_date;
 
-                println!(
-                    "{day:<3} | {interest:<8} | {balance:<7.2} |",
-                    day = i,
-                    interest = daily_interest,
-                    balance = balance
-                );
-            }
-        } else {
-            println!("Number must be a positive whole number (integer)!");
+            fx_position::restore(data.fx_positions);
+        }
+        Ok(None) => {}
+        Err(err) => {
+            println!("Failed to load the data file: {err}");
+
+            return;
         }
     }
-}
 
-fn main() {
-    let mut accounts = Vec::new();
-    let mut exchange_rates = HashMap::<&str, f64>::new();
+    if is_rpc {
+        rpc::serve(&mut rpc::Bank {
+            accounts: &mut accounts,
+            exchange_rates: &mut exchange_rates,
+            exchange_rate_dates: &mut exchange_rate_dates,
+            processed_date: &mut processed_date,
+            limit_table: &withdrawal_limits,
+        });
 
-    for code in currency::CURRENCIES_CODES.iter().skip(1) {
-        exchange_rates.insert(code, 1.0);
+        return;
     }
 
     'main_menu: loop {
+        if let Some(rate_feed) = &mut rate_feed {
+            rate_feed.refresh(&mut exchange_rates, &mut exchange_rate_dates);
+        }
+
+        process_days(
+            &mut accounts,
+            &exchange_rates,
+            &withdrawal_limits,
+            &mut processed_date,
+            clock::today(),
+        );
+
+        if clock::is_simulated() {
+            println!("Bank Date: {}", clock::today());
+
+            println!();
+        }
+
         println!("Select Transaction:");
         print_ordered_list(&TRANSACTION_TITLES);
 
         println!();
 
-        let chosen_idx = prompt("> ").parse::<usize>().unwrap_or_default();
+        let menu_numbers = (1..=TRANSACTION_TITLES.len())
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>();
+        let Some(chosen_idx) = prompt_valid_completing("> ", &menu_numbers, |input| match input.parse::<usize>() {
+            Ok(id) if (1..=TRANSACTION_TITLES.len()).contains(&id) => Ok(id),
+            Ok(_) => Err(String::from("No transaction with this ID exists!")),
+            Err(_) => Err(String::from("ID must be a positive whole number (integer)!")),
+        }) else {
+            println!();
+
+            continue 'main_menu;
+        };
 
         println!();
 
-        if chosen_idx > 0 && chosen_idx <= TRANSACTION_TITLES.len() {
-            println!("{}", TRANSACTION_TITLES[chosen_idx - 1]);
-        }
+        println!("{}", TRANSACTION_TITLES[chosen_idx - 1]);
 
         match chosen_idx {
             1 => {
-                let account = Account::new(prompt("Account Name: "));
+                if let Some(account) = Account::register(accounts.len() + 1) {
+                    println!("Account Number: {}", account.number);
 
-                if !accounts.contains(&account) {
                     accounts.push(account);
-                } else {
-                    println!("An account with this name already exists!");
                 }
             }
             2 | 3 => {
-                if let Some(account) = accounts.iter_mut().find(|a| a.name == prompt("Account Name: ")) {
+                if let Some(idx) = prompt_account_idx(&accounts) {
                     if chosen_idx == 2 {
-                        account.deposit_balance(&exchange_rates);
+                        accounts[idx].deposit_balance(&exchange_rates);
                     } else {
-                        account.withdraw_balance(&exchange_rates);
+                        accounts[idx].withdraw_balance(&exchange_rates, &withdrawal_limits);
                     }
-                } else {
-                    println!("No account with this name exists!");
                 }
             }
-            4 => 'currency_exchange: loop {
+            4 => loop {
                 currency::exchange(&exchange_rates);
 
                 println!();
 
-                'repeat_prompt: loop {
-                    let is_repeating = prompt("Convert another currency? (Y/N): ").to_uppercase();
-
-                    if is_repeating == "Y" {
-                        println!();
-
-                        break 'repeat_prompt;
-                    } else if is_repeating == "N" {
-                        break 'currency_exchange;
-                    } else {
-                        println!("Only accepting a [Y]es or [N]o answer!");
-
-                        println!();
-                    }
+                if confirm("Convert another currency? (Y/N): ") != Some(true) {
+                    break;
                 }
+
+                println!();
             },
             5 => {
                 println!();
 
-                currency::set_exchange_rates(&mut exchange_rates);
+                currency::set_exchange_rates(&mut exchange_rates, &mut exchange_rate_dates);
             }
             6 => {
-                if let Some(account) = accounts.iter().find(|a| a.name == prompt("Account Name: ")) {
-                    account.calculate_interest();
-                } else {
-                    println!("No account with this name exists!");
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    accounts[idx].calculate_interest();
                 }
             }
-            _ => {
-                println!("No transaction with this ID exists!")
+            7 => currency::import_exchange_rates(&mut exchange_rates, &mut exchange_rate_dates),
+            8 => currency::export_exchange_rates(&exchange_rates, &exchange_rate_dates),
+            9 => currency::check_rate_consistency(),
+            10 => list_accounts(&accounts),
+            11 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    accounts[idx].close();
+                }
             }
-        }
+            12 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    accounts[idx].set_overdraft_limit();
+                }
+            }
+            13 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    accounts[idx].pay_loan_installment();
+                }
+            }
+            14 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    accounts[idx].quote_loan_payoff();
+                }
+            }
+            15 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    standing_order::schedule(&mut accounts, idx, clock::today());
+                }
+            }
+            16 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    standing_order::cancel(&mut accounts[idx]);
+                }
+            }
+            17 => {
+                if let Some(day_cnt) = prompt_number(
+                    "Number of Days: ",
+                    0..=clock::MAX_ADVANCE_DAYS,
+                    &format!(
+                        "Number must be a whole number (integer) from 0 to {}!",
+                        clock::MAX_ADVANCE_DAYS
+                    ),
+                ) {
+                    clock::advance_days(day_cnt.into());
 
-        println!();
+                    println!();
 
-        'exit_prompt: loop {
-            let is_continuing = prompt("Back to the Main Menu (Y/N): ").to_uppercase();
+                    process_days(
+                        &mut accounts,
+                        &exchange_rates,
+                        &withdrawal_limits,
+                        &mut processed_date,
+                        clock::today(),
+                    );
 
-            if is_continuing == "Y" {
+                    println!("Bank Date: {}", clock::today());
+                }
+            }
+            18 => {
                 println!();
 
-                break 'exit_prompt;
-            } else if is_continuing == "N" {
-                break 'main_menu;
-            } else {
-                println!("Only accepting a [Y]es or [N]o answer!");
-
+                withdrawal_limit::configure(&mut withdrawal_limits);
+            }
+            19 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    statement::export_statement(&accounts[idx]);
+                }
+            }
+            20 => batch::import_batch(&mut accounts, &exchange_rates, &withdrawal_limits),
+            21 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    accounting_export::export_activity(&accounts[idx]);
+                }
+            }
+            22 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    reconciliation::reconcile_account(&mut accounts[idx]);
+                }
+            }
+            23 => {
                 println!();
+
+                quote_book.request_quote(&exchange_rates);
+            }
+            24 => quote_book.execute_quote(&exchange_rates),
+            25 => fx_position::print_positions(&exchange_rates),
+            26 => scenario::compare_scenarios(&accounts),
+            27 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    savings_goal::set(&mut accounts[idx], &exchange_rates, clock::today());
+                }
+            }
+            28 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    savings_goal::remove(&mut accounts[idx]);
+                }
+            }
+            29 => {
+                if let Some(idx) = prompt_account_idx(&accounts) {
+                    savings_goal::print_goals(&accounts[idx], &exchange_rates, clock::today());
+                }
+            }
+            30 => change_passphrase(&storage::BankDataRef {
+                accounts: &accounts,
+                exchange_rates: &exchange_rates,
+                exchange_rate_dates: &exchange_rate_dates,
+                processed_date,
+                fx_positions: &fx_position::snapshot(),
+            }),
+            _ => unreachable!("menu choices are validated when prompted"),
+        }
+
+        if let Err(err) = storage::save_data(&storage::BankDataRef {
+            accounts: &accounts,
+            exchange_rates: &exchange_rates,
+            exchange_rate_dates: &exchange_rate_dates,
+            processed_date,
+            fx_positions: &fx_position::snapshot(),
+        }) {
+            println!("Warning: Failed to save the data file: {err}");
+        }
+
+        println!();
+
+        loop {
+            match confirm("Back to the Main Menu (Y/N): ") {
+                Some(true) => break,
+                Some(false) => break 'main_menu,
+                None => println!(),
             }
         }
+
+        println!();
     }
 }